@@ -0,0 +1,286 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::candlestick::{Candlestick, CandlestickState, TimeFrame};
+use crate::units::{Price, Volume};
+
+#[derive(Debug, PartialEq)]
+pub enum AggregationError {
+    // A candle is longer than the time frame it should be rolled up into
+    TargetTooShort,
+}
+
+impl fmt::Display for AggregationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AggregationError::TargetTooShort => {
+                write!(f, "can't aggregate candles into a shorter time frame")
+            }
+        }
+    }
+}
+
+impl core::error::Error for AggregationError {}
+
+// Roll candles up into candles of the `target` time frame, e.g. sixty one minute candles
+// into one hour candle. Candles are bucketed by timestamp, so they must be sorted, and
+// candles without a timestamp are skipped. Each aggregated candle starts at its bucket's
+// start, the last one stays Open if its bucket isn't complete yet. Days, weeks and months
+// follow the UTC calendar, see `TimeFrame::bucket_start`.
+pub fn aggregate(
+    candles: &[Candlestick],
+    target: TimeFrame,
+) -> Result<Vec<Candlestick>, AggregationError> {
+    let bucket_seconds = target.seconds();
+    if candles
+        .iter()
+        .any(|candle| candle.time_frame.seconds() > bucket_seconds)
+    {
+        return Err(AggregationError::TargetTooShort);
+    }
+
+    let mut aggregated: Vec<Candlestick> = Vec::new();
+    let mut last_end = 0;
+    for candle in candles {
+        let Some(timestamp) = candle.timestamp else {
+            continue;
+        };
+        let bucket_start = target.bucket_start(timestamp);
+        last_end = candle.time_frame.bucket_end(timestamp);
+
+        match aggregated.last_mut() {
+            Some(current) if current.timestamp == Some(bucket_start) => merge(current, candle),
+            _ => aggregated.push(start_bucket(candle, target, bucket_start)),
+        }
+    }
+
+    if let Some(last) = aggregated.last_mut() {
+        let bucket_end = target.bucket_end(last.timestamp.unwrap_or_default());
+        if last_end < bucket_end {
+            last.state = CandlestickState::Open;
+        }
+    }
+
+    Ok(aggregated)
+}
+
+// A candle of the `target` time frame starting at `bucket_start` with `candle` as its
+// first part.
+pub(crate) fn start_bucket(
+    candle: &Candlestick,
+    target: TimeFrame,
+    bucket_start: i64,
+) -> Candlestick {
+    Candlestick {
+        time_frame: target,
+        timestamp: Some(bucket_start),
+        ..candle.clone()
+    }
+}
+
+// Extend an aggregated candle with the next candle of its bucket, which also decides its
+// state.
+pub(crate) fn merge(current: &mut Candlestick, candle: &Candlestick) {
+    current.high = current.high.max(candle.high);
+    current.low = current.low.min(candle.low);
+    current.close = candle.close;
+    // Saturates rather than wrapping around on an absurd number of trades
+    current.number_of_trades = current
+        .number_of_trades
+        .saturating_add(candle.number_of_trades);
+    current.volume = match (current.volume, candle.volume) {
+        (Some(volume), Some(added)) => Some(volume + added),
+        (volume, added) => volume.or(added),
+    };
+    current.state = candle.state;
+    // Only a bucket made up entirely of filler is synthetic itself
+    current.synthetic &= candle.synthetic;
+}
+
+// Builds candles of one time frame from individual trades.
+pub struct CandleAggregator {
+    time_frame: TimeFrame,
+    current: Option<Candlestick>,
+}
+
+impl CandleAggregator {
+    pub fn new(time_frame: TimeFrame) -> Self {
+        Self {
+            time_frame,
+            current: None,
+        }
+    }
+
+    // The candle still being built, always Open.
+    pub fn current(&self) -> Option<&Candlestick> {
+        self.current.as_ref()
+    }
+
+    // Add a trade to the current candle. A trade in a later bucket closes the current
+    // candle and returns it, a trade older than the current bucket is ignored. So is a
+    // trade with a non-finite or negative price or quantity, which couldn't make a valid
+    // candle. The traded quantities add up to the candle's volume.
+    pub fn on_trade(
+        &mut self,
+        price: impl Into<Price>,
+        quantity: impl Into<Volume>,
+        timestamp: i64,
+    ) -> Option<Candlestick> {
+        let (price, quantity) = (price.into().value(), quantity.into().value());
+        if !(price.is_finite() && price >= 0.0 && quantity.is_finite() && quantity >= 0.0) {
+            return None;
+        }
+        let bucket_start = self.time_frame.bucket_start(timestamp);
+
+        if let Some(current) = self.current.as_mut() {
+            let current_start = current.timestamp.unwrap_or_default();
+            if bucket_start < current_start {
+                return None;
+            }
+            if bucket_start == current_start {
+                current.high = current.high.max(price);
+                current.low = current.low.min(price);
+                current.close = price;
+                current.number_of_trades = current.number_of_trades.saturating_add(1);
+                current.volume = Some(current.volume.unwrap_or_default() + quantity);
+                return None;
+            }
+        }
+
+        let next = Candlestick {
+            open: price,
+            close: price,
+            high: price,
+            low: price,
+            time_frame: self.time_frame,
+            timestamp: Some(bucket_start),
+            number_of_trades: 1,
+            volume: Some(quantity),
+            state: CandlestickState::Open,
+            synthetic: false,
+        };
+        let mut closed = self.current.replace(next)?;
+        closed.state = CandlestickState::Closed;
+        Some(closed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minute(timestamp: i64, close: f64, trades: u32) -> Candlestick {
+        Candlestick::new(
+            close,
+            close + 1.0,
+            close - 1.0,
+            close,
+            TimeFrame::OneMinute,
+            Some(timestamp),
+            trades,
+            Some(Volume::from(10.0)),
+            CandlestickState::Closed,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rolls_minutes_up_into_hours() {
+        let candles: Vec<_> = (0..90)
+            .map(|i| minute(i * 60, 100.0 + i as f64, 2))
+            .collect();
+        let hours = aggregate(&candles, TimeFrame::OneHour).unwrap();
+
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].timestamp, Some(0));
+        assert_eq!(hours[0].open, 100.0);
+        assert_eq!(hours[0].close, 159.0);
+        assert_eq!(hours[0].high, 160.0);
+        assert_eq!(hours[0].low, 99.0);
+        assert_eq!(hours[0].number_of_trades, 120);
+        assert_eq!(hours[0].volume, Some(600.0));
+        assert_eq!(hours[0].state, CandlestickState::Closed);
+        assert_eq!(hours[1].timestamp, Some(3600));
+        assert_eq!(hours[1].state, CandlestickState::Open);
+    }
+
+    #[test]
+    fn rejects_a_shorter_target() {
+        let candles = [minute(0, 100.0, 1)];
+        assert_eq!(
+            aggregate(&candles, TimeFrame::OneMinute).map(|c| c.len()),
+            Ok(1)
+        );
+        let hour = Candlestick {
+            time_frame: TimeFrame::OneHour,
+            ..minute(0, 100.0, 1)
+        };
+        assert_eq!(
+            aggregate(&[hour], TimeFrame::FiveMinutes),
+            Err(AggregationError::TargetTooShort)
+        );
+    }
+
+    #[test]
+    fn trade_counts_saturate() {
+        let candles = [minute(0, 100.0, u32::MAX), minute(60, 101.0, 5)];
+        let hours = aggregate(&candles, TimeFrame::OneHour).unwrap();
+        assert_eq!(hours[0].number_of_trades, u32::MAX);
+    }
+
+    #[test]
+    fn builds_candles_from_trades() {
+        let mut aggregator = CandleAggregator::new(TimeFrame::OneMinute);
+        assert_eq!(aggregator.on_trade(10.0, 1.0, 5), None);
+        assert_eq!(aggregator.on_trade(12.0, 2.0, 30), None);
+        assert_eq!(aggregator.on_trade(9.0, 0.5, 59), None);
+        // Too old for the current bucket
+        assert_eq!(aggregator.on_trade(50.0, 1.0, -1), None);
+
+        let closed = aggregator.on_trade(11.0, 1.0, 61).unwrap();
+        assert_eq!(closed.timestamp, Some(0));
+        assert_eq!(
+            (closed.open, closed.high, closed.low, closed.close),
+            (10.0, 12.0, 9.0, 9.0)
+        );
+        assert_eq!(closed.number_of_trades, 3);
+        assert_eq!(closed.volume, Some(3.5));
+        assert_eq!(closed.state, CandlestickState::Closed);
+
+        let current = aggregator.current().unwrap();
+        assert_eq!(current.timestamp, Some(60));
+        assert_eq!(current.state, CandlestickState::Open);
+
+        // A second boundary closes the candle of the second minute
+        assert_eq!(aggregator.on_trade(13.0, 1.0, 90), None);
+        assert_eq!(aggregator.on_trade(10.5, 1.0, 119), None);
+        let closed = aggregator.on_trade(10.0, 1.0, 120).unwrap();
+        assert_eq!(closed.timestamp, Some(60));
+        assert_eq!(
+            (closed.open, closed.high, closed.low, closed.close),
+            (11.0, 13.0, 10.5, 10.5)
+        );
+        assert_eq!(closed.number_of_trades, 3);
+        assert_eq!(closed.volume, Some(3.0));
+    }
+
+    #[test]
+    fn invalid_trades_are_ignored() {
+        let mut aggregator = CandleAggregator::new(TimeFrame::OneMinute);
+        assert_eq!(aggregator.on_trade(f64::NAN, -5.0, 0), None);
+        assert!(aggregator.current().is_none());
+        assert_eq!(aggregator.on_trade(10.0, 1.0, 0), None);
+        assert_eq!(aggregator.on_trade(-1.0, 1.0, 10), None);
+        assert_eq!(aggregator.on_trade(11.0, f64::INFINITY, 20), None);
+        assert_eq!(aggregator.on_trade(12.0, -1.0, 30), None);
+
+        let closed = aggregator.on_trade(10.0, 1.0, 60).unwrap();
+        assert_eq!(
+            (closed.open, closed.high, closed.low, closed.close),
+            (10.0, 10.0, 10.0, 10.0)
+        );
+        assert_eq!(closed.number_of_trades, 1);
+        assert_eq!(closed.volume, Some(1.0));
+        assert!(closed.is_finite());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::rolling_extrema::RollingExtrema;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AroonResult {
+    // 100 when the highest high of the window is the current candle, 0 when it's
+    // `period` candles old
+    pub up: f64,
+    // The same for the lowest low
+    pub down: f64,
+    // Up minus down
+    pub oscillator: f64,
+}
+
+pub struct Aroon {
+    period: usize,
+    // Highs and lows of the last `period + 1` candles, so an extreme can be up to
+    // `period` candles old.
+    highs: RollingExtrema,
+    lows: RollingExtrema,
+}
+
+impl Aroon {
+    // Panics if `period` is 0, which leaves no room for an extreme to age.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            highs: RollingExtrema::new(period + 1),
+            lows: RollingExtrema::new(period + 1),
+        }
+    }
+
+    // Returns None until `period + 1` candles have been seen. When the extreme is hit
+    // more than once in the window the most recent one counts.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<AroonResult> {
+        self.highs.push(candle.high);
+        self.lows.push(candle.low);
+        if !self.highs.is_full() {
+            return None;
+        }
+
+        let period = self.period as f64;
+        let up = 100.0 * (period - self.highs.max_age()? as f64) / period;
+        let down = 100.0 * (period - self.lows.min_age()? as f64) / period;
+        Some(AroonResult {
+            up: round_to(up, 8),
+            down: round_to(down, 8),
+            oscillator: round_to(up - down, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.highs.clear();
+        self.lows.clear();
+    }
+}
+
+impl Indicator for Aroon {
+    type Output = AroonResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<AroonResult> {
+        Aroon::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        Aroon::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.highs.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64) -> Candlestick {
+        Candlestick::ohlc(high, high, high - 1.0, high)
+    }
+
+    #[test]
+    fn scores_how_recent_the_extremes_are() {
+        let mut aroon = Aroon::new(2);
+        assert_eq!(aroon.bars_until_ready(), 3);
+        assert!(aroon.update(&candle(1.0)).is_none());
+        assert!(aroon.update(&candle(3.0)).is_none());
+
+        let result = aroon.update(&candle(2.0)).unwrap();
+        assert_eq!(
+            (result.up, result.down, result.oscillator),
+            (50.0, 0.0, 50.0)
+        );
+        let result = aroon.update(&candle(4.0)).unwrap();
+        assert_eq!(
+            (result.up, result.down, result.oscillator),
+            (100.0, 50.0, 50.0)
+        );
+        // The most recent of equal lows counts
+        let result = aroon.update(&candle(2.0)).unwrap();
+        assert_eq!((result.up, result.down), (50.0, 100.0));
+
+        aroon.reset();
+        assert_eq!(aroon.bars_until_ready(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        Aroon::new(0);
+    }
+}
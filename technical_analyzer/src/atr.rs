@@ -0,0 +1,118 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+// The largest of the candle's range and the distances from the previous close to its high
+// and low, so gaps between candles count as volatility. Without a previous close this is
+// just the candle's range.
+pub(crate) fn true_range(candle: &Candlestick, previous_close: Option<f64>) -> f64 {
+    let range = candle.high - candle.low;
+    match previous_close {
+        Some(previous_close) => range
+            .max((candle.high - previous_close).abs())
+            .max((candle.low - previous_close).abs()),
+        None => range,
+    }
+}
+
+pub struct AverageTrueRange {
+    period: usize,
+    previous_close: Option<f64>,
+    // True ranges are summed over the first `period` candles to seed the average,
+    // after which Wilder's smoothing takes over.
+    num_processed: usize,
+    average: f64,
+}
+
+impl AverageTrueRange {
+    // Panics if `period` is 0, which has no average.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            previous_close: None,
+            num_processed: 0,
+            average: 0.0,
+        }
+    }
+
+    // Returns None until `period` candles have been seen.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        let true_range = true_range(candle, self.previous_close);
+        self.previous_close = Some(candle.close);
+
+        self.num_processed += 1;
+        let period = self.period as f64;
+        if self.num_processed <= self.period {
+            self.average += true_range / period;
+            if self.num_processed < self.period {
+                return None;
+            }
+        } else {
+            self.average = (self.average * (period - 1.0) + true_range) / period;
+        }
+
+        Some(round_to(self.average, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.previous_close = None;
+        self.num_processed = 0;
+        self.average = 0.0;
+    }
+}
+
+impl Indicator for AverageTrueRange {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        AverageTrueRange::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        AverageTrueRange::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.period.saturating_sub(self.num_processed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_then_smooths_the_true_range() {
+        let mut atr = AverageTrueRange::new(2);
+        assert_eq!(atr.update(&Candlestick::ohlc(10.0, 11.0, 9.0, 10.0)), None);
+        // The gap up makes the true range 16 - 10 = 6, averaged with the first range of 2
+        assert_eq!(
+            atr.update(&Candlestick::ohlc(15.0, 16.0, 14.0, 15.0)),
+            Some(4.0)
+        );
+        assert_eq!(
+            atr.update(&Candlestick::ohlc(15.0, 16.0, 14.0, 15.0)),
+            Some(3.0)
+        );
+        assert_eq!(atr.bars_until_ready(), 0);
+
+        atr.reset();
+        assert_eq!(atr.bars_until_ready(), 2);
+        assert_eq!(atr.update(&Candlestick::ohlc(15.0, 16.0, 14.0, 15.0)), None);
+    }
+
+    #[test]
+    fn true_range_covers_gaps() {
+        let candle = Candlestick::ohlc(10.0, 11.0, 9.5, 10.5);
+        assert_eq!(true_range(&candle, None), 1.5);
+        assert_eq!(true_range(&candle, Some(12.0)), 2.5);
+        assert_eq!(true_range(&candle, Some(8.0)), 3.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        AverageTrueRange::new(0);
+    }
+}
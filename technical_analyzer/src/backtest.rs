@@ -0,0 +1,492 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::candlestick::{Candlestick, TimeFrame};
+use crate::clock::{Clock, ReplayClock};
+use crate::ichimoku::{Cross, IchimokuCloud, IchimokuCloudParameters, IchimokuCloudResult};
+use crate::utils::{round_to, sqrt};
+
+// What a strategy asks for after seeing a candle. The backtester only goes long: Buy
+// opens a position when flat and Sell closes it, anything else is ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    Buy,
+    Sell,
+}
+
+// How much cash a Buy commits. Either way it's capped at the cash available, fees included.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PositionSize {
+    // A fixed amount of cash per trade
+    Fixed(f64),
+    // A fraction of the current equity, 1.0 commits everything
+    PercentOfEquity(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BacktestError {
+    // The commission must be at least 0 and below 1, anything else can eat more than a
+    // trade is worth and push the equity negative
+    InvalidCommission,
+    // Position sizes must be finite and positive, a percentage at most 1.0
+    InvalidPositionSize,
+}
+
+impl fmt::Display for BacktestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BacktestError::InvalidCommission => {
+                write!(f, "commission must be at least 0 and below 1")
+            }
+            BacktestError::InvalidPositionSize => write!(f, "invalid position size"),
+        }
+    }
+}
+
+impl core::error::Error for BacktestError {}
+
+// Indicator values as of the candle handed to the strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorState {
+    pub ichimoku: Option<IchimokuCloudResult>,
+    // Tenkan-sen crossing Kijun-sen on this candle
+    pub tk_cross: Cross,
+    // The replay time of this candle, its timestamp or that of the latest candle before it
+    // that had one, so time-of-day logic doesn't depend on when the backtest runs
+    pub time: i64,
+}
+
+// A closed round trip, the indices are those of the fill candles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    pub entry_index: usize,
+    pub entry_price: f64,
+    pub exit_index: usize,
+    pub exit_price: f64,
+    pub quantity: f64,
+    // Commission paid on entry and exit together
+    pub fees: f64,
+}
+
+impl Trade {
+    // Net of fees.
+    pub fn profit(&self) -> f64 {
+        round_to(
+            (self.exit_price - self.entry_price) * self.quantity - self.fees,
+            8,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    // Closed trades only, a position still open at the end counts towards the equity
+    pub trades: Vec<Trade>,
+    // Cash plus the open position valued at the last close
+    pub final_equity: f64,
+    // Largest decline from a peak of the per-candle equity, as a fraction of that peak
+    pub max_drawdown: f64,
+    // Annualized mean over standard deviation of the per-candle equity returns, assuming
+    // a risk free rate of 0. It's 0 with fewer than two candles or flat equity.
+    pub sharpe_ratio: f64,
+    // Share of the closed trades with a profit, 0 without trades
+    pub win_rate: f64,
+    // Commission paid over the whole run, including the entry of a position still open
+    pub fees: f64,
+}
+
+// Replays candles through a strategy. Orders are filled at the open of the candle after
+// the one that triggered them, an order on the last candle is never filled.
+pub struct Backtester {
+    initial_cash: f64,
+    ichimoku: IchimokuCloud,
+    // Fraction of each fill's value paid as commission, on entry and on exit
+    commission_pct: f64,
+    position_size: PositionSize,
+}
+
+impl Backtester {
+    // A backtester without commission that commits all of its cash to each trade.
+    pub fn new(initial_cash: f64, ichimoku_parameters: IchimokuCloudParameters) -> Self {
+        Self {
+            initial_cash,
+            ichimoku: IchimokuCloud::new(ichimoku_parameters),
+            commission_pct: 0.0,
+            position_size: PositionSize::PercentOfEquity(1.0),
+        }
+    }
+
+    // Charge `commission_pct` of the traded value on every fill, e.g. 0.001 for 0.1%.
+    pub fn commission_pct(mut self, commission_pct: f64) -> Self {
+        self.commission_pct = commission_pct;
+        self
+    }
+
+    pub fn position_size(mut self, position_size: PositionSize) -> Self {
+        self.position_size = position_size;
+        self
+    }
+
+    pub fn run<F>(
+        &mut self,
+        candles: &[Candlestick],
+        mut strategy: F,
+    ) -> Result<BacktestReport, BacktestError>
+    where
+        F: FnMut(&Candlestick, &IndicatorState) -> Option<Order>,
+    {
+        if !(0.0..1.0).contains(&self.commission_pct) {
+            return Err(BacktestError::InvalidCommission);
+        }
+        let valid_size = match self.position_size {
+            PositionSize::Fixed(amount) => amount.is_finite() && amount > 0.0,
+            PositionSize::PercentOfEquity(pct) => pct > 0.0 && pct <= 1.0,
+        };
+        if !valid_size {
+            return Err(BacktestError::InvalidPositionSize);
+        }
+
+        self.ichimoku.reset();
+        let mut clock = ReplayClock::default();
+
+        let mut cash = self.initial_cash;
+        // entry index, price, quantity and entry fee
+        let mut position: Option<(usize, f64, f64, f64)> = None;
+        let mut fees = 0.0;
+        let mut trades = Vec::new();
+        let mut pending: Option<Order> = None;
+        let mut peak = self.initial_cash;
+        let mut max_drawdown: f64 = 0.0;
+        let mut previous_equity = self.initial_cash;
+        let mut returns = Vec::with_capacity(candles.len());
+
+        for (index, candle) in candles.iter().enumerate() {
+            match (pending.take(), position) {
+                (Some(Order::Buy), None) if candle.open > 0.0 => {
+                    // Flat, so the equity is all cash. The fee comes out of the amount
+                    // committed, which keeps the cash from going negative.
+                    let committed = match self.position_size {
+                        PositionSize::Fixed(amount) => amount.min(cash),
+                        PositionSize::PercentOfEquity(pct) => cash * pct,
+                    };
+                    let value = committed / (1.0 + self.commission_pct);
+                    let fee = committed - value;
+                    position = Some((index, candle.open, value / candle.open, fee));
+                    cash -= committed;
+                    fees += fee;
+                }
+                (Some(Order::Sell), Some((entry_index, entry_price, quantity, entry_fee))) => {
+                    let value = quantity * candle.open;
+                    let fee = value * self.commission_pct;
+                    cash += value - fee;
+                    fees += fee;
+                    trades.push(Trade {
+                        entry_index,
+                        entry_price,
+                        exit_index: index,
+                        exit_price: candle.open,
+                        quantity,
+                        fees: round_to(entry_fee + fee, 8),
+                    });
+                    position = None;
+                }
+                _ => {}
+            }
+
+            clock.advance(candle);
+            let (ichimoku, tk_cross) = match self.ichimoku.calculate_with_cross(candle) {
+                Some((result, tk_cross)) => (Some(result), tk_cross),
+                None => (None, Cross::None),
+            };
+            let state = IndicatorState {
+                ichimoku,
+                tk_cross,
+                time: clock.now(),
+            };
+            pending = strategy(candle, &state);
+
+            let equity = cash + position.map_or(0.0, |(_, _, quantity, _)| quantity * candle.close);
+            peak = peak.max(equity);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - equity) / peak);
+            }
+            if previous_equity > 0.0 {
+                returns.push(equity / previous_equity - 1.0);
+            }
+            previous_equity = equity;
+        }
+
+        let final_equity = match (position, candles.last()) {
+            (Some((_, _, quantity, _)), Some(last)) => cash + quantity * last.close,
+            _ => cash,
+        };
+        let win_rate = if trades.is_empty() {
+            0.0
+        } else {
+            let wins = trades.iter().filter(|trade| trade.profit() > 0.0).count();
+            wins as f64 / trades.len() as f64
+        };
+        let sharpe_ratio = candles
+            .first()
+            .map_or(0.0, |first| sharpe_ratio(&returns, first.time_frame));
+
+        Ok(BacktestReport {
+            trades,
+            final_equity: round_to(final_equity, 8),
+            max_drawdown: round_to(max_drawdown, 8),
+            sharpe_ratio: round_to(sharpe_ratio, 8),
+            win_rate: round_to(win_rate, 8),
+            fees: round_to(fees, 8),
+        })
+    }
+}
+
+// Uses the sample standard deviation and scales by the square root of the number of
+// `time_frame` bars in a year.
+fn sharpe_ratio(returns: &[f64], time_frame: TimeFrame) -> f64 {
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean) * (r - mean)).sum::<f64>() / (n - 1.0);
+    if variance <= 0.0 {
+        return 0.0;
+    }
+    mean / sqrt(variance) * sqrt(time_frame.periods_per_year())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // Opens and closes of 10/10, 10/12, 12/15, 15/9 and 9/9
+    fn candles() -> Vec<Candlestick> {
+        [
+            (10.0, 10.0),
+            (10.0, 12.0),
+            (12.0, 15.0),
+            (15.0, 9.0),
+            (9.0, 9.0),
+        ]
+        .iter()
+        .map(|&(open, close)| Candlestick::ohlc(open, open.max(close), open.min(close), close))
+        .collect()
+    }
+
+    // Places `orders[i]` after the i-th candle.
+    fn run(backtester: &mut Backtester, orders: &[Option<Order>]) -> BacktestReport {
+        run_on(backtester, &candles(), orders)
+    }
+
+    fn run_on(
+        backtester: &mut Backtester,
+        candles: &[Candlestick],
+        orders: &[Option<Order>],
+    ) -> BacktestReport {
+        let mut index = 0;
+        backtester
+            .run(candles, |_, _| {
+                index += 1;
+                orders[index - 1]
+            })
+            .unwrap()
+    }
+
+    fn parameters() -> IchimokuCloudParameters {
+        IchimokuCloudParameters::new(1, 2, 3).unwrap()
+    }
+
+    #[test]
+    fn orders_fill_at_the_next_open() {
+        let mut backtester = Backtester::new(1000.0, parameters());
+        let orders = [
+            Some(Order::Buy),
+            None,
+            None,
+            Some(Order::Sell),
+            Some(Order::Buy),
+        ];
+        let report = run(&mut backtester, &orders);
+
+        assert_eq!(report.trades.len(), 1);
+        let trade = &report.trades[0];
+        assert_eq!((trade.entry_index, trade.entry_price), (1, 10.0));
+        assert_eq!((trade.exit_index, trade.exit_price), (4, 9.0));
+        assert_eq!(trade.quantity, 100.0);
+        assert_eq!(trade.profit(), -100.0);
+        // The Buy on the last candle is never filled
+        assert_eq!(report.final_equity, 900.0);
+        // Down from 1500 at the close of the third candle to 900
+        assert_eq!(report.max_drawdown, 0.4);
+        assert_eq!(report.win_rate, 0.0);
+        assert_eq!(report.fees, 0.0);
+    }
+
+    #[test]
+    fn commission_comes_out_of_the_committed_cash() {
+        let mut backtester = Backtester::new(1000.0, parameters())
+            .commission_pct(0.25)
+            .position_size(PositionSize::Fixed(500.0));
+        let orders = [Some(Order::Buy), None, Some(Order::Sell), None, None];
+        let report = run(&mut backtester, &orders);
+
+        // 400 buys 40 at 10 for a fee of 100, selling at 15 gives 600 less 150
+        let trade = &report.trades[0];
+        assert_eq!((trade.quantity, trade.fees), (40.0, 250.0));
+        assert_eq!(trade.profit(), -50.0);
+        assert_eq!(report.final_equity, 950.0);
+        assert_eq!(report.fees, 250.0);
+    }
+
+    #[test]
+    fn commission_is_the_only_difference_in_returns() {
+        let orders = [Some(Order::Buy), None, Some(Order::Sell), None, None];
+        let mut without = Backtester::new(1000.0, parameters());
+        let mut with = Backtester::new(1000.0, parameters()).commission_pct(0.01);
+
+        // Buying and selling at the same price only loses the fees
+        let flat = vec![Candlestick::ohlc(10.0, 10.0, 10.0, 10.0); 5];
+        let gross = run_on(&mut without, &flat, &orders);
+        let net = run_on(&mut with, &flat, &orders);
+        assert_eq!(gross.final_equity, 1000.0);
+        assert!(net.fees > 0.0);
+        assert_eq!(round_to(gross.final_equity - net.final_equity, 8), net.fees);
+
+        // With a price move the fees are the gap between the traded and the net result
+        let net = run(&mut with, &orders);
+        let trade = &net.trades[0];
+        let traded = (trade.exit_price - trade.entry_price) * trade.quantity;
+        assert_eq!((trade.entry_price, trade.exit_price), (10.0, 15.0));
+        assert_eq!(trade.fees, net.fees);
+        assert_eq!(round_to(traded - trade.profit(), 8), net.fees);
+        assert_eq!(round_to(1000.0 + traded - net.fees, 8), net.final_equity);
+        assert!(net.final_equity < run(&mut without, &orders).final_equity);
+    }
+
+    #[test]
+    fn sharpe_ratio_is_annualized_by_the_time_frame() {
+        // Mean 0.005 over a sample deviation of 0.01290994 is 0.38729833 per bar
+        let returns = [0.01, -0.01, 0.02, 0.0];
+        assert_eq!(
+            round_to(sharpe_ratio(&returns, TimeFrame::OneDay), 8),
+            7.39932429
+        );
+        assert_eq!(
+            round_to(sharpe_ratio(&returns, TimeFrame::OneMonth), 8),
+            1.34164079
+        );
+        assert_eq!(sharpe_ratio(&returns[..1], TimeFrame::OneDay), 0.0);
+        assert_eq!(sharpe_ratio(&[0.01, 0.01], TimeFrame::OneDay), 0.0);
+    }
+
+    #[test]
+    fn risk_metrics_of_a_known_equity_curve() {
+        // Holding 10 from 100 takes the equity from 1000 to 1100, 990 and 1089: returns
+        // of 0, 10%, -10% and 10% per bar
+        let daily: Vec<_> = [(100.0, 100.0), (100.0, 110.0), (110.0, 99.0), (99.0, 108.9)]
+            .iter()
+            .map(|&(open, close)| Candlestick {
+                time_frame: TimeFrame::OneDay,
+                ..Candlestick::ohlc(open, open.max(close), open.min(close), close)
+            })
+            .collect();
+        let orders = [Some(Order::Buy), None, None, None];
+        let report = run_on(&mut Backtester::new(1000.0, parameters()), &daily, &orders);
+        assert_eq!(report.final_equity, 1089.0);
+        // From the peak of 1100 down to 990
+        assert_eq!(report.max_drawdown, 0.1);
+        // A mean of 0.025 over a deviation of 0.09574271 is 0.26111648 per bar, times
+        // the root of 365 days
+        assert_eq!(report.sharpe_ratio, 4.98862342);
+
+        let hourly: Vec<_> = daily
+            .iter()
+            .map(|candle| Candlestick {
+                time_frame: TimeFrame::OneHour,
+                ..candle.clone()
+            })
+            .collect();
+        let report = run_on(&mut Backtester::new(1000.0, parameters()), &hourly, &orders);
+        assert_eq!(report.sharpe_ratio, 24.4391638);
+    }
+
+    #[test]
+    fn an_open_position_counts_towards_the_equity() {
+        let mut backtester = Backtester::new(1000.0, parameters());
+        let orders = [Some(Order::Buy), None, None, None, None];
+        let report = run(&mut backtester, &orders);
+        assert!(report.trades.is_empty());
+        assert_eq!(report.final_equity, 900.0);
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let orders = [None; 5];
+        let mut backtester = Backtester::new(1000.0, parameters()).commission_pct(1.0);
+        assert_eq!(
+            backtester.run(&candles(), |_, _| None).err(),
+            Some(BacktestError::InvalidCommission)
+        );
+        let mut backtester =
+            Backtester::new(1000.0, parameters()).position_size(PositionSize::PercentOfEquity(1.5));
+        assert_eq!(
+            backtester.run(&candles(), |_, _| None).err(),
+            Some(BacktestError::InvalidPositionSize)
+        );
+        let mut backtester = Backtester::new(1000.0, parameters());
+        assert_eq!(run(&mut backtester, &orders).final_equity, 1000.0);
+    }
+
+    #[test]
+    fn tk_crossover_strategy() {
+        // A zigzag between 100 and 120, up and down 4 a bar, each candle opening at the
+        // previous close
+        let closes: Vec<f64> = (0..=40)
+            .map(|i| 100.0 + 4.0 * (5 - (i % 10 - 5_i32).abs()) as f64)
+            .collect();
+        let candles: Vec<_> = closes
+            .windows(2)
+            .map(|pair| {
+                let (open, close) = (pair[0], pair[1]);
+                Candlestick::ohlc(open, open.max(close) + 1.0, open.min(close) - 1.0, close)
+            })
+            .collect();
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut backtester = Backtester::new(1000.0, parameters);
+        let report = backtester
+            .run(&candles, |_, state| match state.tk_cross {
+                Cross::BullishTk => Some(Order::Buy),
+                Cross::BearishTk => Some(Order::Sell),
+                Cross::None => None,
+            })
+            .unwrap();
+
+        let fills: Vec<_> = report
+            .trades
+            .iter()
+            .map(|trade| {
+                (
+                    trade.entry_index,
+                    trade.entry_price,
+                    trade.exit_index,
+                    trade.exit_price,
+                )
+            })
+            .collect();
+        // The lines lag the turns, so every trade buys 112 on the way up out of a trough
+        // and sells 108 on the way down from the next peak
+        assert_eq!(report.trades.len(), 3);
+        assert_eq!(
+            fills,
+            [
+                (13, 112.0, 18, 108.0),
+                (23, 112.0, 28, 108.0),
+                (33, 112.0, 38, 108.0)
+            ]
+        );
+        // 1000 * (108 / 112)^3
+        assert_eq!(report.final_equity, 896.63811953);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::candlestick::Candlestick;
+use crate::ichimoku::{IchimokuCloud, IchimokuCloudResult};
+
+// Run a fresh cloud over one symbol's candles, in order, keeping every result.
+fn compute_symbol(
+    candles: &[Candlestick],
+    make_indicator: &impl Fn() -> IchimokuCloud,
+) -> Vec<Option<IchimokuCloudResult>> {
+    let mut cloud = make_indicator();
+    candles
+        .iter()
+        .map(|candle| cloud.calculate(candle))
+        .collect()
+}
+
+// The Ichimoku results of every symbol, one per candle, with a cloud from
+// `make_indicator` for each symbol.
+pub fn compute_serial(
+    symbols: &HashMap<String, Vec<Candlestick>>,
+    make_indicator: impl Fn() -> IchimokuCloud,
+) -> HashMap<String, Vec<Option<IchimokuCloudResult>>> {
+    symbols
+        .iter()
+        .map(|(symbol, candles)| (symbol.clone(), compute_symbol(candles, &make_indicator)))
+        .collect()
+}
+
+// Same as `compute_serial`, but the symbols are spread over rayon's thread pool. Each
+// symbol's candles are still processed one after the other by a single cloud, so the
+// results are identical.
+#[cfg(feature = "rayon")]
+pub fn compute_parallel(
+    symbols: &HashMap<String, Vec<Candlestick>>,
+    make_indicator: impl Fn() -> IchimokuCloud + Sync,
+) -> HashMap<String, Vec<Option<IchimokuCloudResult>>> {
+    symbols
+        .par_iter()
+        .map(|(symbol, candles)| (symbol.clone(), compute_symbol(candles, &make_indicator)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ichimoku::IchimokuCloudParameters;
+
+    fn symbols() -> HashMap<String, Vec<Candlestick>> {
+        let ramp = |start: f64| {
+            (0..8)
+                .map(|i| {
+                    let price = start + i as f64;
+                    Candlestick::ohlc(price, price + 1.0, price - 1.0, price)
+                })
+                .collect::<Vec<_>>()
+        };
+        HashMap::from([
+            ("AAA".to_string(), ramp(10.0)),
+            ("BBB".to_string(), ramp(50.0)),
+        ])
+    }
+
+    fn make_indicator() -> IchimokuCloud {
+        IchimokuCloud::new(IchimokuCloudParameters::new(1, 2, 3).unwrap())
+    }
+
+    #[test]
+    fn each_symbol_gets_its_own_cloud() {
+        let symbols = symbols();
+        let results = compute_serial(&symbols, make_indicator);
+        assert_eq!(results.len(), 2);
+        for (symbol, candles) in &symbols {
+            let mut cloud = make_indicator();
+            let expected: Vec<_> = candles.iter().map(|c| cloud.calculate(c)).collect();
+            assert_eq!(results[symbol], expected);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_serial() {
+        let symbols = symbols();
+        assert_eq!(
+            compute_parallel(&symbols, make_indicator),
+            compute_serial(&symbols, make_indicator)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::rolling_std_dev::RollingStdDev;
+use crate::units::Price;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BollingerResult {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+    // Distance between the upper and lower bands
+    pub bandwidth: f64,
+    // Where the close sits between the bands, 0 at the lower and 1 at the upper band,
+    // outside 0..1 beyond them. 0.5 when the bands have no width.
+    pub percent_b: f64,
+    // The bandwidth relative to the middle band, 0 when the middle band is 0
+    pub normalized_bandwidth: f64,
+}
+
+pub struct BollingerBands {
+    std_devs: f64,
+    window: RollingStdDev,
+}
+
+impl BollingerBands {
+    // Panics if `period` is 0, which has no average.
+    pub fn new(period: usize, std_devs: f64) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            std_devs,
+            window: RollingStdDev::new(period),
+        }
+    }
+
+    // The middle band is the SMA of the last `period` closes, the outer bands are
+    // `std_devs` population standard deviations away from it.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<BollingerResult> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let std_dev = self.window.update(close)?;
+        let middle = self.window.mean()?;
+        let offset = self.std_devs * std_dev;
+        let (upper, lower) = (middle + offset, middle - offset);
+        let width = upper - lower;
+        let percent_b = if width == 0.0 {
+            0.5
+        } else {
+            (close - lower) / width
+        };
+        let normalized_bandwidth = if middle == 0.0 { 0.0 } else { width / middle };
+
+        Some(BollingerResult {
+            upper: round_to(upper, 8),
+            middle: round_to(middle, 8),
+            lower: round_to(lower, 8),
+            bandwidth: round_to(width, 8),
+            percent_b: round_to(percent_b, 8),
+            normalized_bandwidth: round_to(normalized_bandwidth, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+impl Indicator for BollingerBands {
+    type Output = BollingerResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<BollingerResult> {
+        BollingerBands::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        BollingerBands::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_prices_collapse_the_bands() {
+        let mut bands = BollingerBands::new(5, 2.0);
+        for _ in 0..4 {
+            assert!(bands.update(3.0).is_none());
+        }
+        let result = bands.update(3.0).unwrap();
+        assert_eq!(
+            (result.upper, result.middle, result.lower, result.bandwidth),
+            (3.0, 3.0, 3.0, 0.0)
+        );
+        assert_eq!(result.percent_b, 0.5);
+        assert_eq!(result.normalized_bandwidth, 0.0);
+    }
+
+    #[test]
+    fn bands_sit_the_population_std_dev_away() {
+        // Mean 5 and standard deviation 2
+        let mut bands = BollingerBands::new(8, 1.0);
+        let result = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+            .iter()
+            .filter_map(|&close| bands.update(close))
+            .last()
+            .unwrap();
+        assert_eq!((result.upper, result.middle, result.lower), (7.0, 5.0, 3.0));
+        assert_eq!(result.bandwidth, 4.0);
+        assert_eq!(result.percent_b, 1.5);
+        assert_eq!(result.normalized_bandwidth, 0.8);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        BollingerBands::new(0, 2.0);
+    }
+
+    #[test]
+    fn close_on_the_upper_band_is_one() {
+        // Mean 2 and standard deviation 1, the close of 3 is the upper band
+        let mut bands = BollingerBands::new(2, 1.0);
+        bands.update(1.0);
+        let result = bands.update(3.0).unwrap();
+        assert_eq!(result.upper, 3.0);
+        assert_eq!(result.percent_b, 1.0);
+        // Mean 2 again, the close of 1 is now the lower band
+        let result = bands.update(1.0).unwrap();
+        assert_eq!((result.lower, result.percent_b), (1.0, 0.0));
+    }
+}
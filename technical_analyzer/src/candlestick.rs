@@ -0,0 +1,702 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate};
+
+use crate::units::{Price, Volume};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeFrame {
+    #[cfg_attr(feature = "serde", serde(rename = "1m"))]
+    OneMinute,
+    #[cfg_attr(feature = "serde", serde(rename = "5m"))]
+    FiveMinutes,
+    #[cfg_attr(feature = "serde", serde(rename = "15m"))]
+    FifteenMinutes,
+    #[cfg_attr(feature = "serde", serde(rename = "30m"))]
+    ThirtyMinutes,
+    #[cfg_attr(feature = "serde", serde(rename = "1h"))]
+    OneHour,
+    #[cfg_attr(feature = "serde", serde(rename = "4h"))]
+    FourHours,
+    #[cfg_attr(feature = "serde", serde(rename = "1d"))]
+    OneDay,
+    #[cfg_attr(feature = "serde", serde(rename = "1w"))]
+    OneWeek,
+    #[cfg_attr(feature = "serde", serde(rename = "1M"))]
+    OneMonth,
+}
+
+impl TimeFrame {
+    // Nominal length of one bar in seconds, for comparing time frames and sizing buffers.
+    // A month is counted as 30 days, so don't step timestamps by it: `bucket_end`, `shift`
+    // and `bars_between` follow the calendar.
+    pub fn seconds(&self) -> i64 {
+        match self {
+            TimeFrame::OneMinute => 60,
+            TimeFrame::FiveMinutes => 5 * 60,
+            TimeFrame::FifteenMinutes => 15 * 60,
+            TimeFrame::ThirtyMinutes => 30 * 60,
+            TimeFrame::OneHour => 60 * 60,
+            TimeFrame::FourHours => 4 * 60 * 60,
+            TimeFrame::OneDay => 24 * 60 * 60,
+            TimeFrame::OneWeek => 7 * 24 * 60 * 60,
+            TimeFrame::OneMonth => 30 * 24 * 60 * 60,
+        }
+    }
+
+    // Start of the bar `timestamp` falls in. Intraday bars are multiples of `seconds` since
+    // the epoch, which puts days at UTC midnight. Weeks start on Monday and months on the
+    // first, both at UTC midnight, as exchanges count them.
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        let calendar_start = match self {
+            TimeFrame::OneWeek => utc_date(timestamp).and_then(|date| {
+                date.checked_sub_days(Days::new(date.weekday().num_days_from_monday().into()))
+            }),
+            TimeFrame::OneMonth => utc_date(timestamp).and_then(|date| date.with_day(1)),
+            _ => None,
+        };
+        // Outside the dates chrono can represent fall back to fixed length bars
+        calendar_start
+            .map(start_of_day)
+            .unwrap_or_else(|| timestamp - timestamp.rem_euclid(self.seconds()))
+    }
+
+    // End of the bar `timestamp` falls in, which is the start of the next one. Unlike
+    // `bucket_start(timestamp) + seconds()` this follows the length of each month.
+    pub fn bucket_end(&self, timestamp: i64) -> i64 {
+        let start = self.bucket_start(timestamp);
+        let next_month = match self {
+            TimeFrame::OneMonth => {
+                utc_date(start).and_then(|date| date.checked_add_months(Months::new(1)))
+            }
+            _ => None,
+        };
+        next_month
+            .map(start_of_day)
+            .unwrap_or_else(|| start + self.seconds())
+    }
+
+    // `timestamp` moved by `bars` bars, backwards if negative. Months move by calendar
+    // months, keeping the day and time of day, or the last day of a shorter month.
+    pub fn shift(&self, timestamp: i64, bars: i64) -> i64 {
+        let shifted = match self {
+            TimeFrame::OneMonth => DateTime::from_timestamp(timestamp, 0).and_then(|time| {
+                let months = Months::new(u32::try_from(bars.unsigned_abs()).ok()?);
+                let time = time.naive_utc();
+                if bars < 0 {
+                    time.checked_sub_months(months)
+                } else {
+                    time.checked_add_months(months)
+                }
+            }),
+            _ => None,
+        };
+        shifted
+            .map(|time| time.and_utc().timestamp())
+            .unwrap_or_else(|| timestamp + bars * self.seconds())
+    }
+
+    // Number of whole bars from `from` to `to`, negative if `to` comes first. Months count
+    // calendar months, a month that isn't complete yet doesn't count.
+    pub fn bars_between(&self, from: i64, to: i64) -> i64 {
+        if let (TimeFrame::OneMonth, Some(first), Some(last)) = (self, utc_date(from), utc_date(to))
+        {
+            let months = (last.year() as i64 * 12 + last.month0() as i64)
+                - (first.year() as i64 * 12 + first.month0() as i64);
+            // Compare how far into their months both are
+            let into_first = from - self.bucket_start(from);
+            let into_last = to - self.bucket_start(to);
+            return months - i64::from(into_last < into_first);
+        }
+        (to - from).div_euclid(self.seconds())
+    }
+
+    // Number of bars in a 365 day year, for annualizing per-bar statistics. Months
+    // count as twelve a year rather than the 30 day approximation of `seconds`.
+    pub fn periods_per_year(&self) -> f64 {
+        match self {
+            TimeFrame::OneMonth => 12.0,
+            _ => (365 * 24 * 60 * 60) as f64 / self.seconds() as f64,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseTimeFrameError(String);
+
+impl fmt::Display for ParseTimeFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown time frame {:?}", self.0)
+    }
+}
+
+impl core::error::Error for ParseTimeFrameError {}
+
+fn utc_date(timestamp: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|time| time.date_naive())
+}
+
+fn start_of_day(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .map_or(0, |time| time.and_utc().timestamp())
+}
+
+// Parses the short names "1m", "5m", "15m", "30m", "1h", "4h", "1d", "1w" and "1M".
+impl FromStr for TimeFrame {
+    type Err = ParseTimeFrameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(TimeFrame::OneMinute),
+            "5m" => Ok(TimeFrame::FiveMinutes),
+            "15m" => Ok(TimeFrame::FifteenMinutes),
+            "30m" => Ok(TimeFrame::ThirtyMinutes),
+            "1h" => Ok(TimeFrame::OneHour),
+            "4h" => Ok(TimeFrame::FourHours),
+            "1d" => Ok(TimeFrame::OneDay),
+            "1w" => Ok(TimeFrame::OneWeek),
+            "1M" => Ok(TimeFrame::OneMonth),
+            _ => Err(ParseTimeFrameError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CandlestickState {
+    Open,
+    Closed,
+}
+
+// Deserializing goes through `Candlestick::new`, so serde input is validated like any
+// other.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawCandlestick"))]
+pub struct Candlestick {
+    // Prices and volume are plain numbers inside the crate to keep the indicator maths
+    // readable, they are typed as `Price` and `Volume` at the API.
+    pub(crate) open: f64,
+    pub(crate) close: f64,
+    pub(crate) high: f64,
+    pub(crate) low: f64,
+    pub(crate) time_frame: TimeFrame,
+    pub(crate) timestamp: Option<i64>,
+    pub(crate) number_of_trades: u32,
+    // Traded volume, when the source provides it. Indicators that can't work without it,
+    // like VWAP, return None when it's missing. Others fall back to the number of trades,
+    // see `volume_or_trades`.
+    pub(crate) volume: Option<f64>,
+    pub(crate) state: CandlestickState,
+    // Made up to fill a gap in the data rather than received, see `fill_gaps`
+    pub(crate) synthetic: bool,
+}
+
+// The serialized form of a `Candlestick`, before it's validated.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawCandlestick {
+    open: f64,
+    close: f64,
+    high: f64,
+    low: f64,
+    time_frame: TimeFrame,
+    timestamp: Option<i64>,
+    number_of_trades: u32,
+    #[serde(default)]
+    volume: Option<f64>,
+    state: CandlestickState,
+    #[serde(default)]
+    synthetic: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawCandlestick> for Candlestick {
+    type Error = CandleError;
+
+    fn try_from(raw: RawCandlestick) -> Result<Self, CandleError> {
+        let candle = Candlestick::new(
+            raw.open,
+            raw.high,
+            raw.low,
+            raw.close,
+            raw.time_frame,
+            raw.timestamp,
+            raw.number_of_trades,
+            raw.volume.map(Volume::from),
+            raw.state,
+        )?;
+        Ok(Candlestick {
+            synthetic: raw.synthetic,
+            ..candle
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CandleError {
+    NonFinitePrice,
+    NegativePrice,
+    HighBelowLow,
+    OpenOutOfRange,
+    CloseOutOfRange,
+    InvalidVolume,
+    // A required field was never set on a `CandlestickBuilder`
+    MissingField(&'static str),
+}
+
+impl fmt::Display for CandleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CandleError::NonFinitePrice => write!(f, "prices must be finite"),
+            CandleError::NegativePrice => write!(f, "prices must not be negative"),
+            CandleError::HighBelowLow => write!(f, "high is below low"),
+            CandleError::OpenOutOfRange => write!(f, "open is outside the high/low range"),
+            CandleError::CloseOutOfRange => write!(f, "close is outside the high/low range"),
+            CandleError::InvalidVolume => write!(f, "volume must be finite and not negative"),
+            CandleError::MissingField(field) => write!(f, "{} was not set", field),
+        }
+    }
+}
+
+impl core::error::Error for CandleError {}
+
+impl Candlestick {
+    // Build a candlestick, rejecting prices that can't describe a real candle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        open: impl Into<Price>,
+        high: impl Into<Price>,
+        low: impl Into<Price>,
+        close: impl Into<Price>,
+        time_frame: TimeFrame,
+        timestamp: Option<i64>,
+        number_of_trades: u32,
+        volume: Option<Volume>,
+        state: CandlestickState,
+    ) -> Result<Candlestick, CandleError> {
+        let (open, high, low, close) = (
+            open.into().value(),
+            high.into().value(),
+            low.into().value(),
+            close.into().value(),
+        );
+        let volume = volume.map(Volume::value);
+        let prices = [open, high, low, close];
+        if prices.iter().any(|price| !price.is_finite()) {
+            return Err(CandleError::NonFinitePrice);
+        }
+        if prices.iter().any(|&price| price < 0.0) {
+            return Err(CandleError::NegativePrice);
+        }
+        if high < low {
+            return Err(CandleError::HighBelowLow);
+        }
+        if open > high || open < low {
+            return Err(CandleError::OpenOutOfRange);
+        }
+        if close > high || close < low {
+            return Err(CandleError::CloseOutOfRange);
+        }
+        if volume.is_some_and(|volume| !volume.is_finite() || volume < 0.0) {
+            return Err(CandleError::InvalidVolume);
+        }
+
+        Ok(Candlestick {
+            open,
+            close,
+            high,
+            low,
+            time_frame,
+            timestamp,
+            number_of_trades,
+            volume,
+            state,
+            synthetic: false,
+        })
+    }
+
+    pub fn builder() -> CandlestickBuilder {
+        CandlestickBuilder::default()
+    }
+
+    pub fn open(&self) -> Price {
+        Price::from(self.open)
+    }
+
+    pub fn high(&self) -> Price {
+        Price::from(self.high)
+    }
+
+    pub fn low(&self) -> Price {
+        Price::from(self.low)
+    }
+
+    pub fn close(&self) -> Price {
+        Price::from(self.close)
+    }
+
+    pub fn time_frame(&self) -> TimeFrame {
+        self.time_frame
+    }
+
+    pub fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+
+    pub fn number_of_trades(&self) -> u32 {
+        self.number_of_trades
+    }
+
+    pub fn volume(&self) -> Option<Volume> {
+        self.volume.map(Volume::from)
+    }
+
+    // The volume if known, otherwise the number of trades as a stand-in.
+    pub fn volume_or_trades(&self) -> f64 {
+        self.volume.unwrap_or(f64::from(self.number_of_trades))
+    }
+
+    pub fn state(&self) -> CandlestickState {
+        self.state
+    }
+
+    pub fn is_synthetic(&self) -> bool {
+        self.synthetic
+    }
+
+    // Whether every price and the volume, if any, is a real number. Always true for
+    // candles from `new`, which rejects the rest.
+    pub fn is_finite(&self) -> bool {
+        [self.open, self.high, self.low, self.close]
+            .iter()
+            .chain(self.volume.as_ref())
+            .all(|value| value.is_finite())
+    }
+}
+
+// Step by step construction of a `Candlestick`. The prices and time frame are required,
+// the timestamp and volume default to None, the number of trades to 0 and the state
+// to Closed.
+#[derive(Default)]
+pub struct CandlestickBuilder {
+    open: Option<Price>,
+    high: Option<Price>,
+    low: Option<Price>,
+    close: Option<Price>,
+    time_frame: Option<TimeFrame>,
+    timestamp: Option<i64>,
+    number_of_trades: u32,
+    volume: Option<Volume>,
+    state: Option<CandlestickState>,
+}
+
+impl CandlestickBuilder {
+    pub fn open(mut self, open: impl Into<Price>) -> Self {
+        self.open = Some(open.into());
+        self
+    }
+
+    pub fn high(mut self, high: impl Into<Price>) -> Self {
+        self.high = Some(high.into());
+        self
+    }
+
+    pub fn low(mut self, low: impl Into<Price>) -> Self {
+        self.low = Some(low.into());
+        self
+    }
+
+    pub fn close(mut self, close: impl Into<Price>) -> Self {
+        self.close = Some(close.into());
+        self
+    }
+
+    pub fn time_frame(mut self, time_frame: TimeFrame) -> Self {
+        self.time_frame = Some(time_frame);
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn trades(mut self, number_of_trades: u32) -> Self {
+        self.number_of_trades = number_of_trades;
+        self
+    }
+
+    pub fn volume(mut self, volume: impl Into<Volume>) -> Self {
+        self.volume = Some(volume.into());
+        self
+    }
+
+    pub fn state(mut self, state: CandlestickState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    // Validates the candle the same way as `Candlestick::new`.
+    pub fn build(self) -> Result<Candlestick, CandleError> {
+        Candlestick::new(
+            self.open.ok_or(CandleError::MissingField("open"))?,
+            self.high.ok_or(CandleError::MissingField("high"))?,
+            self.low.ok_or(CandleError::MissingField("low"))?,
+            self.close.ok_or(CandleError::MissingField("close"))?,
+            self.time_frame
+                .ok_or(CandleError::MissingField("time_frame"))?,
+            self.timestamp,
+            self.number_of_trades,
+            self.volume,
+            self.state.unwrap_or(CandlestickState::Closed),
+        )
+    }
+}
+
+// A Closed one minute candle without timestamp or volume, for the tests of the indicators
+// to adjust with struct update syntax.
+#[cfg(test)]
+impl Candlestick {
+    pub(crate) fn ohlc(open: f64, high: f64, low: f64, close: f64) -> Self {
+        Candlestick::new(
+            open,
+            high,
+            low,
+            close,
+            TimeFrame::OneMinute,
+            None,
+            1,
+            None,
+            CandlestickState::Closed,
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn candle() -> Candlestick {
+        Candlestick::builder()
+            .open(10.0)
+            .high(12.5)
+            .low(9.5)
+            .close(11.0)
+            .time_frame(TimeFrame::OneHour)
+            .timestamp(3_600)
+            .trades(42)
+            .volume(1_250.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn clones_compare_equal() {
+        let original = candle();
+        assert_eq!(original.clone(), original);
+        let other = Candlestick::builder()
+            .open(10.0)
+            .high(12.5)
+            .low(9.5)
+            .close(11.5)
+            .time_frame(TimeFrame::OneHour)
+            .build()
+            .unwrap();
+        assert_ne!(other, original);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let original = candle();
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains("\"time_frame\":\"1h\""));
+        let parsed: Candlestick = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, original);
+
+        let untimed = Candlestick {
+            timestamp: None,
+            ..original
+        };
+        let json = serde_json::to_string(&untimed).unwrap();
+        assert!(json.contains("\"timestamp\":null"));
+        assert_eq!(serde_json::from_str::<Candlestick>(&json).unwrap(), untimed);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_is_validated() {
+        let json = r#"{"open":10.0,"close":11.0,"high":9.0,"low":12.0,"time_frame":"1h",
+            "timestamp":null,"number_of_trades":0,"state":"Closed"}"#;
+        let error = serde_json::from_str::<Candlestick>(json).unwrap_err();
+        assert_eq!(error.to_string(), CandleError::HighBelowLow.to_string());
+
+        let json = r#"{"open":10.0,"close":11.0,"high":12.0,"low":9.0,"time_frame":"1h",
+            "timestamp":null,"number_of_trades":0,"state":"Closed","volume":-1.0}"#;
+        assert!(serde_json::from_str::<Candlestick>(json).is_err());
+
+        // Volume and the synthetic flag may be left out
+        let json = r#"{"open":10.0,"close":11.0,"high":12.0,"low":9.0,"time_frame":"1h",
+            "timestamp":7200,"number_of_trades":3,"state":"Open"}"#;
+        let candle: Candlestick = serde_json::from_str(json).unwrap();
+        assert_eq!((candle.volume(), candle.is_synthetic()), (None, false));
+    }
+
+    #[test]
+    fn months_follow_the_calendar() {
+        // 2024-01-31 12:00, 2024-02-29 12:00 and 2024-03-01 UTC
+        let (january, february, march) = (1_706_702_400, 1_709_208_000, 1_709_251_200);
+        let month = TimeFrame::OneMonth;
+        assert_eq!(month.bucket_end(january), 1_706_745_600);
+        assert_eq!(month.bucket_end(february), march);
+        assert_eq!(month.shift(january, 1), february);
+        assert_eq!(month.shift(march, -2), 1_704_067_200);
+        assert_eq!(month.bars_between(january, february), 0);
+        assert_eq!(month.bars_between(january, march), 1);
+        assert_eq!(month.bars_between(march, january), -2);
+        assert_eq!(TimeFrame::OneHour.shift(3_600, 26), 97_200);
+        assert_eq!(TimeFrame::OneHour.bars_between(3_600, 97_199), 25);
+    }
+
+    #[test]
+    fn time_frame_and_state_are_copied() {
+        let original = candle();
+        let (first, second) = (original.time_frame, original.time_frame);
+        assert_eq!((first, second), (TimeFrame::OneHour, TimeFrame::OneHour));
+        let (open, closed) = (CandlestickState::Open, original.state);
+        assert_ne!(open, closed);
+        assert_eq!(original.state, CandlestickState::Closed);
+    }
+
+    #[test]
+    fn invalid_candles_are_rejected() {
+        let build = |open: f64, high: f64, low: f64, close: f64, volume: Option<f64>| {
+            Candlestick::new(
+                open,
+                high,
+                low,
+                close,
+                TimeFrame::OneMinute,
+                None,
+                1,
+                volume.map(Volume::from),
+                CandlestickState::Closed,
+            )
+        };
+        assert_eq!(
+            build(10.0, f64::NAN, 9.0, 10.0, None),
+            Err(CandleError::NonFinitePrice)
+        );
+        assert_eq!(
+            build(10.0, f64::INFINITY, 9.0, 10.0, None),
+            Err(CandleError::NonFinitePrice)
+        );
+        assert_eq!(
+            build(0.0, 1.0, -1.0, 0.0, None),
+            Err(CandleError::NegativePrice)
+        );
+        assert_eq!(
+            build(10.0, 9.0, 11.0, 10.0, None),
+            Err(CandleError::HighBelowLow)
+        );
+        assert_eq!(
+            build(12.0, 11.0, 9.0, 10.0, None),
+            Err(CandleError::OpenOutOfRange)
+        );
+        assert_eq!(
+            build(10.0, 11.0, 9.0, 8.0, None),
+            Err(CandleError::CloseOutOfRange)
+        );
+        assert_eq!(
+            build(10.0, 11.0, 9.0, 10.0, Some(-1.0)),
+            Err(CandleError::InvalidVolume)
+        );
+        assert_eq!(
+            build(10.0, 11.0, 9.0, 10.0, Some(f64::NAN)),
+            Err(CandleError::InvalidVolume)
+        );
+        assert!(build(10.0, 11.0, 9.0, 10.0, Some(0.0)).is_ok());
+    }
+
+    #[test]
+    fn builder_reports_the_missing_field() {
+        let missing_high = Candlestick::builder()
+            .open(10.0)
+            .low(9.5)
+            .close(11.0)
+            .time_frame(TimeFrame::OneHour)
+            .build();
+        assert_eq!(missing_high, Err(CandleError::MissingField("high")));
+        assert_eq!(missing_high.unwrap_err().to_string(), "high was not set");
+
+        let defaults = Candlestick::builder()
+            .open(10.0)
+            .high(12.5)
+            .low(9.5)
+            .close(11.0)
+            .time_frame(TimeFrame::OneHour)
+            .build()
+            .unwrap();
+        assert_eq!(
+            (
+                defaults.timestamp(),
+                defaults.number_of_trades(),
+                defaults.volume(),
+                defaults.state()
+            ),
+            (None, 0, None, CandlestickState::Closed)
+        );
+    }
+
+    #[test]
+    fn time_frames_parse_from_short_names() {
+        let parsed: Vec<_> = ["1m", "5m", "1h", "1d", "1M"]
+            .iter()
+            .map(|name| {
+                let time_frame: TimeFrame = name.parse().unwrap();
+                (time_frame, time_frame.seconds())
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                (TimeFrame::OneMinute, 60),
+                (TimeFrame::FiveMinutes, 300),
+                (TimeFrame::OneHour, 3_600),
+                (TimeFrame::OneDay, 86_400),
+                (TimeFrame::OneMonth, 2_592_000),
+            ]
+        );
+        let error = "2h".parse::<TimeFrame>().unwrap_err();
+        assert_eq!(error, ParseTimeFrameError("2h".to_string()));
+        assert_eq!(error.to_string(), "unknown time frame \"2h\"");
+    }
+
+    #[test]
+    fn intraday_and_weekly_time_frames() {
+        let parsed: Vec<_> = ["15m", "30m", "4h", "1w"]
+            .iter()
+            .map(|name| {
+                let time_frame: TimeFrame = name.parse().unwrap();
+                (time_frame, time_frame.seconds())
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                (TimeFrame::FifteenMinutes, 900),
+                (TimeFrame::ThirtyMinutes, 1_800),
+                (TimeFrame::FourHours, 14_400),
+                (TimeFrame::OneWeek, 604_800),
+            ]
+        );
+        assert!("15M".parse::<TimeFrame>().is_err());
+        assert!("4H".parse::<TimeFrame>().is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+use crate::window::Window;
+
+pub struct CommodityChannelIndex {
+    // Typical prices, (high + low + close) / 3, of the last `period` candles
+    window: Window<f64>,
+}
+
+impl CommodityChannelIndex {
+    // Panics if `period` is 0, which has no average.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            window: Window::new(period),
+        }
+    }
+
+    // How far the typical price is from its average, in units of 0.015 mean absolute
+    // deviations. Returns None until the window is full, and 0 when the window has no
+    // deviation at all.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        let typical_price = (candle.high + candle.low + candle.close) / 3.0;
+        self.window.push(typical_price);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let n = self.window.len() as f64;
+        let average = self.window.iter().sum::<f64>() / n;
+        let mean_deviation = self
+            .window
+            .iter()
+            .map(|price| (price - average).abs())
+            .sum::<f64>()
+            / n;
+        if mean_deviation == 0.0 {
+            return Some(0.0);
+        }
+        Some(round_to(
+            (typical_price - average) / (0.015 * mean_deviation),
+            8,
+        ))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Indicator for CommodityChannelIndex {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        CommodityChannelIndex::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        CommodityChannelIndex::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(price: f64) -> Candlestick {
+        Candlestick::ohlc(price, price, price, price)
+    }
+
+    #[test]
+    fn measures_the_distance_in_mean_deviations() {
+        let mut cci = CommodityChannelIndex::new(5);
+        for _ in 0..4 {
+            assert_eq!(cci.update(&flat(10.0)), None);
+        }
+        // Average 11 and mean deviation 1.6
+        assert_eq!(cci.update(&flat(15.0)), Some(166.66666667));
+
+        let mut cci = CommodityChannelIndex::new(5);
+        for _ in 0..4 {
+            cci.update(&flat(10.0));
+        }
+        assert_eq!(cci.update(&flat(5.0)), Some(-166.66666667));
+    }
+
+    #[test]
+    fn no_deviation_gives_0() {
+        let mut cci = CommodityChannelIndex::new(2);
+        cci.update(&flat(3.0));
+        assert_eq!(cci.update(&flat(3.0)), Some(0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        CommodityChannelIndex::new(0);
+    }
+}
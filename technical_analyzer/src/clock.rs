@@ -0,0 +1,88 @@
+use crate::candlestick::Candlestick;
+use crate::session::SECONDS_PER_DAY;
+
+// Where time-dependent logic gets the current time from, as Unix seconds, so a backtest
+// can run on the candles' time instead of the wall clock.
+pub trait Clock {
+    fn now(&self) -> i64;
+
+    // The UTC calendar day `now` falls on, counted from the epoch.
+    fn day(&self) -> i64 {
+        self.now().div_euclid(SECONDS_PER_DAY)
+    }
+}
+
+// The wall clock.
+#[cfg(feature = "std")]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        since_epoch.as_secs() as i64
+    }
+}
+
+// Time as of the latest candle replayed, i.e. the timestamp of that candle, starting at
+// the epoch. The same candles always give the same times.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReplayClock {
+    now: i64,
+}
+
+impl ReplayClock {
+    pub fn new(start: i64) -> Self {
+        Self { now: start }
+    }
+
+    // Move to the candle's timestamp, returning whether it falls on a later UTC day than
+    // the clock did, e.g. to reset a session indicator. Candles without a timestamp leave
+    // the clock where it is. The clock never runs backwards, an older timestamp is ignored.
+    pub fn advance(&mut self, candle: &Candlestick) -> bool {
+        let Some(timestamp) = candle.timestamp else {
+            return false;
+        };
+        if timestamp <= self.now {
+            return false;
+        }
+        let previous_day = self.day();
+        self.now = timestamp;
+        self.day() != previous_day
+    }
+}
+
+impl Clock for ReplayClock {
+    fn now(&self) -> i64 {
+        self.now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: Option<i64>) -> Candlestick {
+        Candlestick {
+            timestamp,
+            ..Candlestick::ohlc(1.0, 1.0, 1.0, 1.0)
+        }
+    }
+
+    #[test]
+    fn replay_follows_the_candles_forward() {
+        let mut clock = ReplayClock::default();
+        assert_eq!((clock.now(), clock.day()), (0, 0));
+        assert!(!clock.advance(&at(Some(60))));
+        assert_eq!(clock.now(), 60);
+        assert!(!clock.advance(&at(None)));
+        assert!(!clock.advance(&at(Some(30))));
+        assert_eq!(clock.now(), 60);
+        assert!(clock.advance(&at(Some(SECONDS_PER_DAY))));
+        assert_eq!(clock.day(), 1);
+
+        assert_eq!(ReplayClock::new(-1).day(), -1);
+    }
+}
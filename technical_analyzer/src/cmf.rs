@@ -0,0 +1,112 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// Chaikin Money Flow, the volume-weighted average of where the closes sit in their
+// candles' ranges over the last `period` candles, from -1 to 1.
+pub struct ChaikinMoneyFlow {
+    // Money flow volume and volume of the last `period` candles and their running sums.
+    window: Window<(f64, f64)>,
+    money_flow_volume_sum: f64,
+    volume_sum: f64,
+}
+
+impl ChaikinMoneyFlow {
+    // Panics if `period` is 0, which has no volume.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            window: Window::new(period),
+            money_flow_volume_sum: 0.0,
+            volume_sum: 0.0,
+        }
+    }
+
+    // The money flow multiplier is ((close - low) - (high - close)) / (high - low), 1 for
+    // a close at the high and -1 at the low. A candle without any range counts as 0.
+    // Returns None until `period` candles have been seen, and for a candle without
+    // volume, which is skipped. A window without any volume gives 0.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        let volume = candle.volume?;
+        let range = candle.high - candle.low;
+        let multiplier = if range > 0.0 {
+            ((candle.close - candle.low) - (candle.high - candle.close)) / range
+        } else {
+            0.0
+        };
+        let money_flow_volume = multiplier * volume;
+
+        self.money_flow_volume_sum += money_flow_volume;
+        self.volume_sum += volume;
+        if let Some((evicted_flow, evicted_volume)) = self.window.push((money_flow_volume, volume))
+        {
+            self.money_flow_volume_sum -= evicted_flow;
+            self.volume_sum -= evicted_volume;
+        }
+        if !self.window.is_full() {
+            return None;
+        }
+
+        if self.volume_sum <= 0.0 {
+            return Some(0.0);
+        }
+        Some(round_to(self.money_flow_volume_sum / self.volume_sum, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.money_flow_volume_sum = 0.0;
+        self.volume_sum = 0.0;
+    }
+}
+
+impl Indicator for ChaikinMoneyFlow {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        ChaikinMoneyFlow::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        ChaikinMoneyFlow::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(close: f64, volume: Option<f64>) -> Candlestick {
+        Candlestick {
+            volume,
+            ..Candlestick::ohlc(5.0, 10.0, 0.0, close)
+        }
+    }
+
+    #[test]
+    fn weighs_the_close_location_by_volume() {
+        let mut cmf = ChaikinMoneyFlow::new(2);
+        assert_eq!(cmf.update(&candle(10.0, Some(1.0))), None);
+        // A close at the high with 1 and at the low with 3
+        assert_eq!(cmf.update(&candle(0.0, Some(3.0))), Some(-0.5));
+        assert_eq!(cmf.update(&candle(10.0, None)), None);
+        // The midpoint counts as 0
+        assert_eq!(cmf.update(&candle(5.0, Some(1.0))), Some(-0.75));
+
+        cmf.reset();
+        assert_eq!(cmf.bars_until_ready(), 2);
+        cmf.update(&candle(10.0, Some(0.0)));
+        assert_eq!(cmf.update(&candle(10.0, Some(0.0))), Some(0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        ChaikinMoneyFlow::new(0);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// Chande momentum oscillator: the difference of the up and down moves of the close over
+// `period` bars as a percentage of all movement, from -100 when every move was down to
+// 100 when every move was up.
+pub struct ChandeMomentum {
+    // Close to close changes of the last `period` bars
+    changes: Window<f64>,
+    previous_close: Option<f64>,
+}
+
+impl ChandeMomentum {
+    // Panics if `period` is 0, which has no moves.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            changes: Window::new(period),
+            previous_close: None,
+        }
+    }
+
+    // Returns None until `period + 1` closes have been seen, and 0 when the close didn't
+    // move at all over the window.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let previous_close = self.previous_close.replace(close)?;
+        self.changes.push(close - previous_close);
+        if !self.changes.is_full() {
+            return None;
+        }
+
+        let (up, down) = self.changes.iter().fold((0.0, 0.0), |(up, down), &change| {
+            if change > 0.0 {
+                (up + change, down)
+            } else {
+                (up, down - change)
+            }
+        });
+        if up + down == 0.0 {
+            return Some(0.0);
+        }
+        Some(round_to(100.0 * (up - down) / (up + down), 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.changes.clear();
+        self.previous_close = None;
+    }
+}
+
+impl Indicator for ChandeMomentum {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        ChandeMomentum::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        ChandeMomentum::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        usize::from(self.previous_close.is_none()) + self.changes.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_up_and_down_moves() {
+        let mut cmo = ChandeMomentum::new(2);
+        assert_eq!(Indicator::bars_until_ready(&cmo), 3);
+        assert_eq!(cmo.update(10.0), None);
+        assert_eq!(cmo.update(12.0), None);
+        // Up 2 and down 1
+        assert_eq!(cmo.update(11.0), Some(33.33333333));
+        assert_eq!(cmo.update(11.0), Some(-100.0));
+        assert_eq!(cmo.update(11.0), Some(0.0));
+
+        cmo.reset();
+        assert_eq!(Indicator::bars_until_ready(&cmo), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        ChandeMomentum::new(0);
+    }
+}
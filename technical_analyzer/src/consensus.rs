@@ -0,0 +1,215 @@
+use crate::ichimoku::IchimokuSignal;
+use crate::macd::MacdResult;
+use crate::utils::round_to;
+
+// The indicator a vote comes from, so each kind can be weighted on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignalSource {
+    Ichimoku,
+    Rsi,
+    Macd,
+    MovingAverage,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Vote {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndicatorSignal {
+    pub source: SignalSource,
+    pub vote: Vote,
+}
+
+impl IndicatorSignal {
+    // Strong and weak signals above the cloud are bullish, below it bearish.
+    pub fn ichimoku(signal: IchimokuSignal) -> Self {
+        let vote = match signal {
+            IchimokuSignal::StrongBull | IchimokuSignal::WeakBull => Vote::Bullish,
+            IchimokuSignal::StrongBear | IchimokuSignal::WeakBear => Vote::Bearish,
+            IchimokuSignal::Neutral => Vote::Neutral,
+        };
+        Self {
+            source: SignalSource::Ichimoku,
+            vote,
+        }
+    }
+
+    // Mean reversion reading of the RSI: oversold, at or below `oversold`, is bullish and
+    // overbought, at or above `overbought`, bearish. The usual thresholds are 30 and 70.
+    pub fn rsi(value: f64, oversold: f64, overbought: f64) -> Self {
+        let vote = if value <= oversold {
+            Vote::Bullish
+        } else if value >= overbought {
+            Vote::Bearish
+        } else {
+            Vote::Neutral
+        };
+        Self {
+            source: SignalSource::Rsi,
+            vote,
+        }
+    }
+
+    // The sign of the histogram, i.e. the MACD line above or below its signal line.
+    pub fn macd(result: &MacdResult) -> Self {
+        Self {
+            source: SignalSource::Macd,
+            vote: vote_from_sign(result.histogram),
+        }
+    }
+
+    // A rising moving average is bullish, a falling one bearish.
+    pub fn moving_average_slope(previous: f64, current: f64) -> Self {
+        Self {
+            source: SignalSource::MovingAverage,
+            vote: vote_from_sign(current - previous),
+        }
+    }
+}
+
+fn vote_from_sign(value: f64) -> Vote {
+    if value > 0.0 {
+        Vote::Bullish
+    } else if value < 0.0 {
+        Vote::Bearish
+    } else {
+        Vote::Neutral
+    }
+}
+
+// How much each indicator's vote counts. A weight of 0 leaves an indicator out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignalWeights {
+    pub ichimoku: f64,
+    pub rsi: f64,
+    pub macd: f64,
+    pub moving_average: f64,
+}
+
+impl SignalWeights {
+    pub fn weight(&self, source: SignalSource) -> f64 {
+        match source {
+            SignalSource::Ichimoku => self.ichimoku,
+            SignalSource::Rsi => self.rsi,
+            SignalSource::Macd => self.macd,
+            SignalSource::MovingAverage => self.moving_average,
+        }
+    }
+}
+
+// Every indicator counts the same.
+impl Default for SignalWeights {
+    fn default() -> Self {
+        Self {
+            ichimoku: 1.0,
+            rsi: 1.0,
+            macd: 1.0,
+            moving_average: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Consensus {
+    // From -1, every weighted vote bearish, to 1, every weighted vote bullish
+    pub score: f64,
+    pub bullish: usize,
+    pub bearish: usize,
+    pub neutral: usize,
+}
+
+// Tally the signals with every indicator weighted the same, see `consensus_with`.
+pub fn consensus(signals: &[IndicatorSignal]) -> Consensus {
+    consensus_with(signals, &SignalWeights::default())
+}
+
+// Tally the votes and score them: bullish votes count +1, bearish -1 and neutral 0, each
+// times its indicator's weight, divided by the total weight. Neutral votes pull the score
+// towards 0. The score is 0 when there are no signals or no weight.
+pub fn consensus_with(signals: &[IndicatorSignal], weights: &SignalWeights) -> Consensus {
+    let mut tally = Consensus {
+        score: 0.0,
+        bullish: 0,
+        bearish: 0,
+        neutral: 0,
+    };
+    let mut weighted_sum = 0.0;
+    let mut total_weight = 0.0;
+    for signal in signals {
+        let weight = weights.weight(signal.source);
+        total_weight += weight;
+        match signal.vote {
+            Vote::Bullish => {
+                tally.bullish += 1;
+                weighted_sum += weight;
+            }
+            Vote::Bearish => {
+                tally.bearish += 1;
+                weighted_sum -= weight;
+            }
+            Vote::Neutral => tally.neutral += 1,
+        }
+    }
+
+    if total_weight > 0.0 {
+        tally.score = round_to(weighted_sum / total_weight, 8);
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn translates_indicator_readings_into_votes() {
+        assert_eq!(
+            IndicatorSignal::ichimoku(IchimokuSignal::WeakBull).vote,
+            Vote::Bullish
+        );
+        assert_eq!(
+            IndicatorSignal::ichimoku(IchimokuSignal::StrongBear).vote,
+            Vote::Bearish
+        );
+        assert_eq!(IndicatorSignal::rsi(30.0, 30.0, 70.0).vote, Vote::Bullish);
+        assert_eq!(IndicatorSignal::rsi(50.0, 30.0, 70.0).vote, Vote::Neutral);
+        assert_eq!(IndicatorSignal::rsi(70.0, 30.0, 70.0).vote, Vote::Bearish);
+        let macd = MacdResult {
+            macd: 1.0,
+            signal: 1.5,
+            histogram: -0.5,
+        };
+        assert_eq!(IndicatorSignal::macd(&macd).vote, Vote::Bearish);
+        assert_eq!(
+            IndicatorSignal::moving_average_slope(1.0, 1.0).vote,
+            Vote::Neutral
+        );
+    }
+
+    #[test]
+    fn scores_the_weighted_votes() {
+        let signals = vec![
+            IndicatorSignal::ichimoku(IchimokuSignal::StrongBull),
+            IndicatorSignal::rsi(80.0, 30.0, 70.0),
+            IndicatorSignal::moving_average_slope(1.0, 2.0),
+            IndicatorSignal::moving_average_slope(2.0, 2.0),
+        ];
+        let tally = consensus(&signals);
+        assert_eq!((tally.bullish, tally.bearish, tally.neutral), (2, 1, 1));
+        assert_eq!(tally.score, 0.25);
+
+        let weights = SignalWeights {
+            ichimoku: 3.0,
+            rsi: 0.0,
+            ..SignalWeights::default()
+        };
+        // (3 + 1) / 5
+        assert_eq!(consensus_with(&signals, &weights).score, 0.8);
+        assert_eq!(consensus(&[]).score, 0.0);
+    }
+}
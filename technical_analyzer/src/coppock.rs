@@ -0,0 +1,93 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::{chained, Indicator};
+use crate::momentum::RateOfChange;
+use crate::units::Price;
+use crate::wma::WeightedMovingAverage;
+
+// Weighted moving average of the sum of a long and a short rate of change of the closes,
+// a slow momentum gauge meant for monthly closes. Turning up from below zero is the
+// classic buy signal.
+pub struct Coppock {
+    roc_long: RateOfChange,
+    roc_short: RateOfChange,
+    wma: WeightedMovingAverage,
+}
+
+impl Coppock {
+    // Panics if `wma_period` is 0.
+    pub fn new(roc_long: usize, roc_short: usize, wma_period: usize) -> Self {
+        Self {
+            roc_long: RateOfChange::new(roc_long),
+            roc_short: RateOfChange::new(roc_short),
+            wma: WeightedMovingAverage::new(wma_period),
+        }
+    }
+
+    // Returns None until both rates of change have `wma_period` values to average, i.e.
+    // after the longer ROC period plus `wma_period` closes. A close for which a rate of
+    // change is undefined, because the close it compares against was zero, is left out
+    // of the average.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let long = self.roc_long.update(close);
+        let short = self.roc_short.update(close);
+        self.wma.update(long? + short?)
+    }
+
+    pub fn reset(&mut self) {
+        self.roc_long.reset();
+        self.roc_short.reset();
+        self.wma.reset();
+    }
+}
+
+// The usual 14/11/10 configuration.
+impl Default for Coppock {
+    fn default() -> Self {
+        Self::new(14, 11, 10)
+    }
+}
+
+impl Indicator for Coppock {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        Coppock::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        Coppock::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        chained(
+            self.roc_long
+                .bars_until_ready()
+                .max(self.roc_short.bars_until_ready()),
+            self.wma.bars_until_ready(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_average_of_both_rates_of_change() {
+        let mut coppock = Coppock::new(2, 1, 2);
+        assert_eq!(Indicator::bars_until_ready(&coppock), 4);
+        for close in [100.0, 100.0, 110.0] {
+            assert_eq!(coppock.update(close), None);
+        }
+        // Sums of 10 + 10 and 21 + 10, the latest weighing double
+        assert_eq!(coppock.update(121.0), Some(27.33333333));
+        assert_eq!(Indicator::bars_until_ready(&coppock), 0);
+
+        coppock.reset();
+        assert_eq!(Indicator::bars_until_ready(&coppock), 4);
+    }
+}
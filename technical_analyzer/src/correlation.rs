@@ -0,0 +1,139 @@
+use crate::utils::{round_to, sqrt};
+use crate::window::Window;
+
+// Running sums of a window of pairs.
+#[derive(Default)]
+struct Sums {
+    a: f64,
+    b: f64,
+    aa: f64,
+    bb: f64,
+    ab: f64,
+}
+
+impl Sums {
+    // Add a pair, or take it out again with a `sign` of -1.
+    fn add(&mut self, a: f64, b: f64, sign: f64) {
+        self.a += sign * a;
+        self.b += sign * b;
+        self.aa += sign * a * a;
+        self.bb += sign * b * b;
+        self.ab += sign * a * b;
+    }
+}
+
+// Pearson correlation of two series over the last `period` pairs of values, e.g. the
+// closes of two symbols for pairs trading. Kept up to date with running sums, which are
+// recomputed from the window once every `period` replacements so rounding errors don't
+// build up over a long stream.
+pub struct RollingCorrelation {
+    period: usize,
+    window: Window<(f64, f64)>,
+    sums: Sums,
+    num_replaced: usize,
+}
+
+impl RollingCorrelation {
+    // Panics if `period` is 0, which has no pairs to correlate.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            window: Window::new(period),
+            sums: Sums::default(),
+            num_replaced: 0,
+        }
+    }
+
+    // Add the next value of each series and return their correlation, from -1 to 1, or
+    // None while fewer than `period` pairs have been seen. A series that didn't move over
+    // the window doesn't correlate with anything, that gives 0.
+    pub fn update(&mut self, a: f64, b: f64) -> Option<f64> {
+        if !a.is_finite() || !b.is_finite() {
+            return None;
+        }
+        self.sums.add(a, b, 1.0);
+        if let Some((evicted_a, evicted_b)) = self.window.push((a, b)) {
+            self.sums.add(evicted_a, evicted_b, -1.0);
+            self.num_replaced += 1;
+        }
+        if self.num_replaced >= self.period {
+            self.resync();
+        }
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let n = self.period as f64;
+        let sums = &self.sums;
+        let covariance = n * sums.ab - sums.a * sums.b;
+        let variance_a = n * sums.aa - sums.a * sums.a;
+        let variance_b = n * sums.bb - sums.b * sums.b;
+        // Rounding can leave a flat series with a tiny variance of either sign
+        if variance_a <= 0.0 || variance_b <= 0.0 {
+            return Some(0.0);
+        }
+        let correlation = covariance / sqrt(variance_a * variance_b);
+        Some(round_to(correlation.clamp(-1.0, 1.0), 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sums = Sums::default();
+        self.num_replaced = 0;
+    }
+
+    fn resync(&mut self) {
+        let mut sums = Sums::default();
+        for &(a, b) in self.window.iter() {
+            sums.add(a, b, 1.0);
+        }
+        self.sums = sums;
+        self.num_replaced = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_series_correlate_fully() {
+        let mut correlation = RollingCorrelation::new(3);
+        let mut inverse = RollingCorrelation::new(3);
+        for i in 0..5 {
+            let a = i as f64;
+            let expected = (i >= 2).then_some(1.0);
+            assert_eq!(correlation.update(a, 2.0 * a + 1.0), expected);
+            assert_eq!(inverse.update(a, -a), expected.map(|c| -c));
+        }
+        assert_eq!(correlation.update(f64::NAN, 1.0), None);
+
+        correlation.reset();
+        assert_eq!(correlation.update(1.0, 1.0), None);
+    }
+
+    #[test]
+    fn flat_series_give_0() {
+        let mut correlation = RollingCorrelation::new(2);
+        correlation.update(1.0, 5.0);
+        assert_eq!(correlation.update(2.0, 5.0), Some(0.0));
+    }
+
+    #[test]
+    fn stays_accurate_over_a_long_stream() {
+        let mut correlation = RollingCorrelation::new(4);
+        let mut last = None;
+        for i in 0..10_000 {
+            let a = 1e6 + (i % 5) as f64;
+            last = correlation.update(a, -a);
+        }
+        assert_eq!(last, Some(-1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        RollingCorrelation::new(0);
+    }
+}
@@ -0,0 +1,225 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::candlestick::{Candlestick, CandlestickState, TimeFrame};
+use crate::ichimoku::IchimokuCloudResult;
+use crate::units::Volume;
+use crate::utils::round_to;
+
+const CANDLE_HEADER: &str = "timestamp,open,high,low,close,trades,volume";
+
+fn invalid_data(line_number: usize, message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_number, message),
+    )
+}
+
+fn parse_field<T: FromStr>(line_number: usize, name: &str, value: Option<&str>) -> io::Result<T> {
+    let value = value.ok_or_else(|| invalid_data(line_number, format!("missing {}", name)))?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid_data(line_number, format!("invalid {} {:?}", name, value)))
+}
+
+// Read candles from a CSV file with the columns timestamp,open,high,low,close,trades and
+// an optional volume, which may also be left empty. A header row is skipped if present and
+// every candle is loaded as Closed. Malformed rows fail with an InvalidData error naming
+// the line.
+pub fn read_candles_csv(path: &Path, time_frame: TimeFrame) -> Result<Vec<Candlestick>, io::Error> {
+    let contents = fs::read_to_string(path)?;
+    let mut candles = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split(',');
+        let first = fields.next().unwrap_or_default();
+        if index == 0 && first.trim().parse::<i64>().is_err() {
+            continue;
+        }
+
+        let timestamp = parse_field(line_number, "timestamp", Some(first))?;
+        let open: f64 = parse_field(line_number, "open", fields.next())?;
+        let high: f64 = parse_field(line_number, "high", fields.next())?;
+        let low: f64 = parse_field(line_number, "low", fields.next())?;
+        let close: f64 = parse_field(line_number, "close", fields.next())?;
+        let trades = parse_field(line_number, "trades", fields.next())?;
+        let volume: Option<f64> = match fields.next() {
+            Some(value) if !value.trim().is_empty() => {
+                Some(parse_field(line_number, "volume", Some(value))?)
+            }
+            _ => None,
+        };
+        if fields.next().is_some() {
+            return Err(invalid_data(line_number, "too many columns".to_string()));
+        }
+
+        let candle = Candlestick::new(
+            open,
+            high,
+            low,
+            close,
+            time_frame,
+            Some(timestamp),
+            trades,
+            volume.map(Volume::from),
+            CandlestickState::Closed,
+        )
+        .map_err(|error| invalid_data(line_number, error.to_string()))?;
+        candles.push(candle);
+    }
+
+    Ok(candles)
+}
+
+// The candle columns of a row, in the order of `CANDLE_HEADER`.
+fn candle_row(candle: &Candlestick) -> String {
+    format!(
+        "{},{},{},{},{},{},{}",
+        candle.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+        round_to(candle.open, 8),
+        round_to(candle.high, 8),
+        round_to(candle.low, 8),
+        round_to(candle.close, 8),
+        candle.number_of_trades,
+        candle
+            .volume
+            .map(|v| round_to(v, 8).to_string())
+            .unwrap_or_default(),
+    )
+}
+
+// Write candles in the format read by `read_candles_csv`, with a header row.
+pub fn write_candles_csv(path: &Path, candles: &[Candlestick]) -> Result<(), io::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "{}", CANDLE_HEADER)?;
+    for candle in candles {
+        writeln!(writer, "{}", candle_row(candle))?;
+    }
+    writer.flush()
+}
+
+// Write one row per candle followed by its Ichimoku values, leaving those cells empty
+// where there is no result.
+pub fn write_ichimoku_csv(
+    path: &Path,
+    results: &[(&Candlestick, Option<IchimokuCloudResult>)],
+) -> Result<(), io::Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(
+        writer,
+        "{},tenkan_sen,kijun_sen,senkou_span_a,senkou_span_b,chikou_span",
+        CANDLE_HEADER
+    )?;
+    for (candle, result) in results {
+        let values = match result {
+            Some(result) => format!(
+                "{},{},{},{},{}",
+                round_to(result.tenkan_sen, 8),
+                round_to(result.kijun_sen, 8),
+                round_to(result.senkou_span_a, 8),
+                round_to(result.senkou_span_b, 8),
+                round_to(result.chikou_span, 8),
+            ),
+            None => ",,,,".to_string(),
+        };
+        writeln!(writer, "{},{}", candle_row(candle), values)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "technical_analyzer_{}_{}.csv",
+            name,
+            std::process::id()
+        ));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_rows_with_and_without_volume() {
+        let path = temp_file(
+            "read",
+            "timestamp,open,high,low,close,trades,volume\n\
+             60,1,2,0.5,1.5,3,120.5\n\
+             120,1.5,2.5,1,2,4,\n\
+             180,2,3,1.5,2.5,5\n",
+        );
+        let candles = read_candles_csv(&path, TimeFrame::OneMinute).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles[0].timestamp, Some(60));
+        assert_eq!(candles[0].volume, Some(120.5));
+        assert_eq!(candles[1].volume, None);
+        assert_eq!(candles[2].close, 2.5);
+        assert_eq!(candles[2].number_of_trades, 5);
+        assert_eq!(candles[2].volume, None);
+    }
+
+    #[test]
+    fn names_the_malformed_line() {
+        let path = temp_file("malformed", "60,1,2,0.5,1.5,3\n120,1.5,x,1,2,4\n");
+        let error = read_candles_csv(&path, TimeFrame::OneMinute).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: invalid high \"x\"");
+
+        fs::write(&path, "60,1,2,0.5,1.5,3,-1\n").unwrap();
+        let error = read_candles_csv(&path, TimeFrame::OneMinute).unwrap_err();
+        assert!(error.to_string().starts_with("line 1: "));
+
+        fs::write(&path, "60,1,2,0.5,1.5,3,1,1\n").unwrap();
+        let error = read_candles_csv(&path, TimeFrame::OneMinute).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.to_string(), "line 1: too many columns");
+    }
+
+    #[test]
+    fn round_trips_written_candles() {
+        let candles: Vec<_> = (0..5)
+            .map(|i| {
+                Candlestick::new(
+                    10.0 + i as f64,
+                    12.0 + i as f64,
+                    9.0,
+                    11.0 + i as f64 / 3.0,
+                    TimeFrame::OneMinute,
+                    Some(3_600 + i * 60),
+                    2,
+                    (i % 2 == 0).then(|| Volume::from(7.5 * i as f64)),
+                    CandlestickState::Closed,
+                )
+                .unwrap()
+            })
+            .collect();
+        let path = temp_file("round_trip", "");
+        write_candles_csv(&path, &candles).unwrap();
+        let read = read_candles_csv(&path, TimeFrame::OneMinute).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(
+            text.starts_with("timestamp,open,high,low,close,trades,volume\n3600,10,12,9,11,2,0\n")
+        );
+        let rounded: Vec<_> = candles
+            .iter()
+            .map(|candle| Candlestick {
+                close: round_to(candle.close, 8),
+                ..candle.clone()
+            })
+            .collect();
+        assert_eq!(read, rounded);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::atr::true_range;
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DmiResult {
+    pub plus_di: f64,
+    pub minus_di: f64,
+    pub adx: f64,
+}
+
+// How strong a trend the ADX reports, whatever its direction. The usual reading: below 20
+// there's no trend, 20 to 25 a weak one, above 25 a strong one and above 50 a very strong
+// one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrendStrength {
+    NoTrend,
+    Weak,
+    Strong,
+    VeryStrong,
+}
+
+impl TrendStrength {
+    pub fn from_adx(adx: f64) -> Self {
+        if adx > 50.0 {
+            TrendStrength::VeryStrong
+        } else if adx > 25.0 {
+            TrendStrength::Strong
+        } else if adx >= 20.0 {
+            TrendStrength::Weak
+        } else {
+            TrendStrength::NoTrend
+        }
+    }
+}
+
+// Wilder's directional movement system.
+pub struct DirectionalMovement {
+    period: usize,
+    previous: Option<(f64, f64, f64)>,
+    // Wilder-smoothed true range, +DM and -DM. They start out as plain sums over the first
+    // `period` candles.
+    num_movements: usize,
+    smoothed_true_range: f64,
+    smoothed_plus_dm: f64,
+    smoothed_minus_dm: f64,
+    // The ADX is seeded with the average of the first `period` DX values.
+    num_dx: usize,
+    adx: f64,
+    // The ADX before the latest one, for its slope
+    previous_adx: Option<f64>,
+}
+
+impl DirectionalMovement {
+    // Panics if `period` is 0, which has nothing to smooth over.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            previous: None,
+            num_movements: 0,
+            smoothed_true_range: 0.0,
+            smoothed_plus_dm: 0.0,
+            smoothed_minus_dm: 0.0,
+            num_dx: 0,
+            adx: 0.0,
+            previous_adx: None,
+        }
+    }
+
+    // Returns None until the ADX is available, which takes `2 * period` candles.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<DmiResult> {
+        let (previous_high, previous_low, previous_close) =
+            self.previous
+                .replace((candle.high, candle.low, candle.close))?;
+
+        let up_move = candle.high - previous_high;
+        let down_move = previous_low - candle.low;
+        let plus_dm = if up_move > down_move && up_move > 0.0 {
+            up_move
+        } else {
+            0.0
+        };
+        let minus_dm = if down_move > up_move && down_move > 0.0 {
+            down_move
+        } else {
+            0.0
+        };
+        let true_range = true_range(candle, Some(previous_close));
+
+        self.num_movements += 1;
+        let period = self.period as f64;
+        if self.num_movements <= self.period {
+            self.smoothed_true_range += true_range;
+            self.smoothed_plus_dm += plus_dm;
+            self.smoothed_minus_dm += minus_dm;
+            if self.num_movements < self.period {
+                return None;
+            }
+        } else {
+            self.smoothed_true_range += true_range - self.smoothed_true_range / period;
+            self.smoothed_plus_dm += plus_dm - self.smoothed_plus_dm / period;
+            self.smoothed_minus_dm += minus_dm - self.smoothed_minus_dm / period;
+        }
+
+        let (plus_di, minus_di) = if self.smoothed_true_range == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (
+                100.0 * self.smoothed_plus_dm / self.smoothed_true_range,
+                100.0 * self.smoothed_minus_dm / self.smoothed_true_range,
+            )
+        };
+        let dx = if plus_di + minus_di == 0.0 {
+            0.0
+        } else {
+            100.0 * (plus_di - minus_di).abs() / (plus_di + minus_di)
+        };
+
+        self.num_dx += 1;
+        if self.num_dx <= self.period {
+            self.adx += dx / period;
+            if self.num_dx < self.period {
+                return None;
+            }
+        } else {
+            self.previous_adx = Some(self.adx);
+            self.adx = (self.adx * (period - 1.0) + dx) / period;
+        }
+
+        Some(DmiResult {
+            plus_di: round_to(plus_di, 8),
+            minus_di: round_to(minus_di, 8),
+            adx: round_to(self.adx, 8),
+        })
+    }
+
+    // The strength of the trend as of the latest ADX, None until the ADX is available.
+    pub fn trend(&self) -> Option<TrendStrength> {
+        self.adx().map(TrendStrength::from_adx)
+    }
+
+    // Whether the latest ADX is above the one before it, i.e. the trend is getting
+    // stronger. None until two ADX values have been computed.
+    pub fn is_rising(&self) -> Option<bool> {
+        let adx = self.adx()?;
+        let previous_adx = self.previous_adx?;
+        Some(adx > round_to(previous_adx, 8))
+    }
+
+    fn adx(&self) -> Option<f64> {
+        (self.period > 0 && self.num_dx >= self.period).then(|| round_to(self.adx, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.num_movements = 0;
+        self.smoothed_true_range = 0.0;
+        self.smoothed_plus_dm = 0.0;
+        self.smoothed_minus_dm = 0.0;
+        self.num_dx = 0;
+        self.adx = 0.0;
+        self.previous_adx = None;
+    }
+}
+
+impl Indicator for DirectionalMovement {
+    type Output = DmiResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<DmiResult> {
+        DirectionalMovement::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        DirectionalMovement::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        // The first candle only sets the previous one. The first DX takes `period`
+        // movements and the ADX `period` DX values, that first DX being one of them.
+        usize::from(self.previous.is_none())
+            + (2 * self.period).saturating_sub(1 + self.num_movements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn rising(i: usize) -> Candlestick {
+        let price = 100.0 + 2.0 * i as f64;
+        Candlestick::ohlc(price, price + 1.0, price - 1.0, price)
+    }
+
+    #[test]
+    fn a_steady_rise_is_all_plus_movement() {
+        let mut dmi = DirectionalMovement::new(3);
+        assert_eq!(dmi.bars_until_ready(), 6);
+        for i in 0..5 {
+            assert!(dmi.update(&rising(i)).is_none());
+        }
+        assert_eq!(dmi.trend(), None);
+        // Every bar moves up 2 with a true range of 3
+        let result = dmi.update(&rising(5)).unwrap();
+        assert_eq!(
+            (result.plus_di, result.minus_di, result.adx),
+            (66.66666667, 0.0, 100.0)
+        );
+        assert_eq!(dmi.trend(), Some(TrendStrength::VeryStrong));
+        assert_eq!(dmi.is_rising(), None);
+        dmi.update(&rising(6));
+        assert_eq!(dmi.is_rising(), Some(false));
+
+        dmi.reset();
+        assert_eq!(dmi.bars_until_ready(), 6);
+        assert_eq!(dmi.trend(), None);
+    }
+
+    #[test]
+    fn adx_rises_as_a_chop_turns_into_a_trend() {
+        let mut dmi = DirectionalMovement::new(5);
+        let mut readings = Vec::new();
+        let mut price = 100.0;
+        for i in 0..40 {
+            let step = match i {
+                0..=19 if i % 2 == 0 => 1.0,
+                0..=19 => -1.0,
+                _ => 0.2 * (i - 19) as f64,
+            };
+            let open = price;
+            price += step;
+            dmi.update(&Candlestick::ohlc(
+                open,
+                open.max(price) + 1.0,
+                open.min(price) - 1.0,
+                price,
+            ));
+            if let Some(trend) = dmi.trend() {
+                readings.push((trend, dmi.is_rising()));
+            }
+        }
+        assert_eq!(readings[0].1, None);
+        let weak = readings.iter().position(|(t, _)| *t == TrendStrength::Weak);
+        let strong = readings
+            .iter()
+            .position(|(t, _)| *t == TrendStrength::Strong);
+        assert!(weak.unwrap() < strong.unwrap());
+        assert_eq!(readings[strong.unwrap()].1, Some(true));
+    }
+
+    #[test]
+    fn trend_strength_thresholds() {
+        assert_eq!(TrendStrength::from_adx(19.9), TrendStrength::NoTrend);
+        assert_eq!(TrendStrength::from_adx(20.0), TrendStrength::Weak);
+        assert_eq!(TrendStrength::from_adx(25.0), TrendStrength::Weak);
+        assert_eq!(TrendStrength::from_adx(25.1), TrendStrength::Strong);
+        assert_eq!(TrendStrength::from_adx(50.1), TrendStrength::VeryStrong);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        DirectionalMovement::new(0);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::candlestick::Candlestick;
+use crate::high_low::HighLowWindow;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DonchianResult {
+    pub upper: f64,
+    pub lower: f64,
+    pub middle: f64,
+}
+
+pub struct DonchianChannels {
+    window: HighLowWindow,
+}
+
+impl DonchianChannels {
+    // Panics if `period` is 0, which has no range.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            window: HighLowWindow::new(period),
+        }
+    }
+
+    // The highest high and lowest low of the last `period` candles and their midpoint.
+    // Returns None until the window is full.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<DonchianResult> {
+        self.window.push(candle.high, candle.low);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let upper = self.window.highest();
+        let lower = self.window.lowest();
+        Some(DonchianResult {
+            upper,
+            lower,
+            middle: round_to((upper + lower) / 2.0, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Indicator for DonchianChannels {
+    type Output = DonchianResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<DonchianResult> {
+        DonchianChannels::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        DonchianChannels::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_the_extremes_of_the_window() {
+        let mut donchian = DonchianChannels::new(2);
+        assert!(donchian
+            .update(&Candlestick::ohlc(10.0, 12.0, 8.0, 10.0))
+            .is_none());
+        let result = donchian
+            .update(&Candlestick::ohlc(10.0, 11.0, 9.0, 10.0))
+            .unwrap();
+        assert_eq!(
+            (result.upper, result.lower, result.middle),
+            (12.0, 8.0, 10.0)
+        );
+        // The first candle drops out of the window
+        let result = donchian
+            .update(&Candlestick::ohlc(10.0, 10.5, 9.5, 10.0))
+            .unwrap();
+        assert_eq!(
+            (result.upper, result.lower, result.middle),
+            (11.0, 9.0, 10.0)
+        );
+
+        donchian.reset();
+        assert_eq!(donchian.bars_until_ready(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        DonchianChannels::new(0);
+    }
+}
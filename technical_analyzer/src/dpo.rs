@@ -0,0 +1,86 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::sma::SimpleMovingAverage;
+use crate::units::Price;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// Detrended price oscillator: the close `period / 2 + 1` bars ago minus the SMA of the
+// last `period` closes. Comparing an older close with the average centres the average on
+// it, which strips out trends longer than the period and leaves the shorter cycles.
+pub struct DetrendedPriceOscillator {
+    sma: SimpleMovingAverage,
+    // The last `period / 2 + 2` closes, so the oldest one is the close to compare
+    closes: Window<f64>,
+}
+
+impl DetrendedPriceOscillator {
+    // Panics if `period` is 0.
+    pub fn new(period: usize) -> Self {
+        Self {
+            sma: SimpleMovingAverage::new(period),
+            closes: Window::new(period / 2 + 2),
+        }
+    }
+
+    // Returns None until both the SMA window and the lookback are filled, i.e. after
+    // `period` closes or `period / 2 + 2` for the shortest periods.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        self.closes.push(close);
+        let average = self.sma.update(close)?;
+        if !self.closes.is_full() {
+            return None;
+        }
+        let past = self.closes.oldest()?;
+        Some(round_to(past - average, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.sma.reset();
+        self.closes.clear();
+    }
+}
+
+impl Indicator for DetrendedPriceOscillator {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        DetrendedPriceOscillator::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        DetrendedPriceOscillator::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.sma.bars_until_ready().max(self.closes.remaining())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_an_older_close_with_the_average() {
+        let mut dpo = DetrendedPriceOscillator::new(4);
+        for close in 1..4 {
+            assert_eq!(dpo.update(close as f64), None);
+        }
+        // The close 3 bars ago against the average of 1 to 4
+        assert_eq!(dpo.update(4.0), Some(-1.5));
+        assert_eq!(dpo.update(10.0), Some(-2.75));
+    }
+
+    #[test]
+    fn short_periods_wait_for_the_lookback() {
+        let mut dpo = DetrendedPriceOscillator::new(1);
+        assert_eq!(Indicator::bars_until_ready(&dpo), 2);
+        assert_eq!(dpo.update(1.0), None);
+        assert_eq!(dpo.update(3.0), Some(-2.0));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+
+pub struct ExponentialMovingAverage {
+    period: usize,
+    // Smoothing factor, 2 / (period + 1)
+    alpha: f64,
+    // Seed from the average of the first `period` closes instead of from the first close
+    sma_seed: bool,
+    num_processed: usize,
+    sum: f64,
+    value: Option<f64>,
+}
+
+impl ExponentialMovingAverage {
+    // An average seeded from the first close it sees. Panics if `period` is 0, which would
+    // overshoot every close.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            sma_seed: false,
+            num_processed: 0,
+            sum: 0.0,
+            value: None,
+        }
+    }
+
+    // An average seeded from the SMA of the first `period` closes, which is closer to the
+    // true EMA early on. Until then the average of the closes seen so far is returned.
+    pub fn new_seeded(period: usize) -> Self {
+        Self {
+            sma_seed: true,
+            ..Self::new(period)
+        }
+    }
+
+    // Returns the average including this close, or None for a non-finite close, which is
+    // skipped.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let value = self.next(close);
+        self.num_processed += 1;
+        if self.sma_seed && self.num_processed <= self.period {
+            self.sum += close;
+        }
+        self.value = Some(value);
+
+        Some(round_to(value, 8))
+    }
+
+    // The value `update` would return for this close, without taking it in. Not rounded,
+    // so callers can round to their own precision.
+    pub(crate) fn peek(&self, close: f64) -> f64 {
+        self.next(close)
+    }
+
+    // The current value, None before the first close, not rounded either.
+    pub(crate) fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    fn next(&self, close: f64) -> f64 {
+        let num_processed = self.num_processed + 1;
+        match self.value {
+            _ if self.sma_seed && num_processed <= self.period => {
+                (self.sum + close) / num_processed as f64
+            }
+            Some(previous) => previous + self.alpha * (close - previous),
+            None => close,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.num_processed = 0;
+        self.sum = 0.0;
+        self.value = None;
+    }
+}
+
+impl Indicator for ExponentialMovingAverage {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        ExponentialMovingAverage::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        ExponentialMovingAverage::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        // Seeded or not, there is a value from the first close on
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_average_starts_from_the_sma() {
+        let mut ema = ExponentialMovingAverage::new_seeded(3);
+        assert_eq!(ema.update(1.0), Some(1.0));
+        assert_eq!(ema.update(2.0), Some(1.5));
+        assert_eq!(ema.update(3.0), Some(2.0));
+        assert_eq!(ema.update(6.0), Some(4.0));
+
+        let mut ema = ExponentialMovingAverage::new(3);
+        assert_eq!(ema.update(1.0), Some(1.0));
+        assert_eq!(ema.update(3.0), Some(2.0));
+    }
+
+    #[test]
+    fn skips_non_finite_closes() {
+        let mut ema = ExponentialMovingAverage::new(3);
+        assert_eq!(ema.update(f64::NAN), None);
+        assert_eq!(ema.value(), None);
+        assert_eq!(ema.update(1.0), Some(1.0));
+        assert_eq!(ema.update(f64::INFINITY), None);
+        assert_eq!(ema.update(3.0), Some(2.0));
+        assert_eq!(
+            Indicator::update(&mut ema, &Candlestick::ohlc(3.0, 3.0, 3.0, 3.0)),
+            Some(2.5)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        ExponentialMovingAverage::new(0);
+    }
+}
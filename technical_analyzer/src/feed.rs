@@ -0,0 +1,133 @@
+use std::fmt;
+
+use futures_util::StreamExt;
+use tokio::sync::mpsc;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::{self, Message};
+
+use crate::candlestick::{Candlestick, TimeFrame};
+use crate::jsonl::{parse_candle, ParseError};
+
+// Candles received but not yet taken from the receiver. When it's full the feed stops
+// reading from the socket until there is room again.
+const CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug)]
+pub enum FeedError {
+    WebSocket(Box<tungstenite::Error>),
+    Parse(ParseError),
+    // A message a custom `CandleFormat` couldn't make sense of
+    InvalidMessage(String),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedError::WebSocket(error) => write!(f, "websocket: {}", error),
+            FeedError::Parse(error) => write!(f, "{}", error),
+            FeedError::InvalidMessage(message) => write!(f, "invalid message: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for FeedError {}
+
+impl From<tungstenite::Error> for FeedError {
+    fn from(error: tungstenite::Error) -> Self {
+        FeedError::WebSocket(Box::new(error))
+    }
+}
+
+impl From<ParseError> for FeedError {
+    fn from(error: ParseError) -> Self {
+        FeedError::Parse(error)
+    }
+}
+
+// Turns the text messages of one exchange's stream into candles. Messages that aren't
+// candles, like heartbeats or subscription replies, give Ok(None) and are skipped.
+pub trait CandleFormat: Send + 'static {
+    fn parse(&mut self, message: &str) -> Result<Option<Candlestick>, FeedError>;
+}
+
+// One flat JSON object per message, in the format of `read_candles_jsonl`: the candle is
+// Closed unless the message has "closed": false. Errors number the messages as lines,
+// counting from 1.
+pub struct JsonCandleFormat {
+    time_frame: TimeFrame,
+    received: usize,
+}
+
+impl JsonCandleFormat {
+    pub fn new(time_frame: TimeFrame) -> Self {
+        Self {
+            time_frame,
+            received: 0,
+        }
+    }
+}
+
+impl CandleFormat for JsonCandleFormat {
+    fn parse(&mut self, message: &str) -> Result<Option<Candlestick>, FeedError> {
+        self.received += 1;
+        Ok(Some(parse_candle(self.received, message, self.time_frame)?))
+    }
+}
+
+// Connect to a WebSocket candle stream and forward its candles, parsed by `format`, to
+// the returned receiver. Reading happens on a task spawned on the current tokio runtime.
+// A message that fails to parse is passed on as an error and the feed goes on, a
+// WebSocket error is passed on and ends it. The receiver yields None once the server
+// closed the stream. After the receiver is dropped the connection is closed on the next
+// candle.
+pub async fn connect_feed<F: CandleFormat>(
+    url: &str,
+    mut format: F,
+) -> Result<mpsc::Receiver<Result<Candlestick, FeedError>>, FeedError> {
+    let (mut stream, _) = connect_async(url).await?;
+    let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+
+    tokio::spawn(async move {
+        while let Some(message) = stream.next().await {
+            let (candle, last) = match message {
+                Ok(Message::Text(text)) => match format.parse(&text) {
+                    Ok(Some(candle)) => (Ok(candle), false),
+                    Ok(None) => continue,
+                    Err(error) => (Err(error), false),
+                },
+                Ok(Message::Close(_)) => break,
+                // Pings are answered by the stream itself
+                Ok(_) => continue,
+                Err(error) => (Err(error.into()), true),
+            };
+            if sender.send(candle).await.is_err() || last {
+                break;
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_messages_are_numbered_like_lines() {
+        let mut format = JsonCandleFormat::new(TimeFrame::OneHour);
+        let candle = format
+            .parse(r#"{"timestamp": 3600, "open": 1, "high": 2, "low": 0.5, "close": 1.5}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(candle.timestamp, Some(3600));
+        assert_eq!(candle.time_frame, TimeFrame::OneHour);
+
+        let error = format.parse(r#"{"open": 1}"#).unwrap_err();
+        assert!(matches!(
+            error,
+            FeedError::Parse(ParseError::MissingField { line: 2, .. })
+        ));
+        assert!(error.to_string().contains("line 2"));
+    }
+}
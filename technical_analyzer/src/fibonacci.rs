@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+
+use crate::renko::Direction;
+use crate::units::Price;
+use crate::utils::round_to;
+
+const RETRACEMENT_RATIOS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+
+// The standard Fibonacci ratios paired with their price levels along a swing. Ratios are
+// measured from where the swing started, so for an Up swing 0 is the swing low and 1 the
+// swing high, and for a Down swing the other way around. Levels come out in ratio order,
+// i.e. ascending in price for an Up swing and descending for a Down swing.
+pub fn fib_retracements(
+    swing_high: impl Into<Price>,
+    swing_low: impl Into<Price>,
+    direction: Direction,
+) -> Vec<(f64, f64)> {
+    let (swing_high, swing_low) = (swing_high.into().value(), swing_low.into().value());
+    let (start, end) = match direction {
+        Direction::Up => (swing_low, swing_high),
+        Direction::Down => (swing_high, swing_low),
+    };
+
+    RETRACEMENT_RATIOS
+        .iter()
+        .map(|&ratio| (ratio, round_to(start + ratio * (end - start), 8)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_run_from_the_start_of_the_swing() {
+        let up = fib_retracements(200.0, 100.0, Direction::Up);
+        assert_eq!(
+            up,
+            [
+                (0.0, 100.0),
+                (0.236, 123.6),
+                (0.382, 138.2),
+                (0.5, 150.0),
+                (0.618, 161.8),
+                (0.786, 178.6),
+                (1.0, 200.0),
+            ]
+        );
+        let down = fib_retracements(200.0, 100.0, Direction::Down);
+        assert_eq!(down[0], (0.0, 200.0));
+        assert_eq!(down[2], (0.382, 161.8));
+        assert_eq!(down[6], (1.0, 100.0));
+    }
+}
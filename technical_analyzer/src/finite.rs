@@ -0,0 +1,128 @@
+use core::fmt;
+
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+
+// What `FiniteGuard` does with a candle holding a NaN or infinite price or volume. A
+// single NaN in a rolling min/max or running sum stays there, so no indicator takes one
+// in: `Candlestick::new`, which deserialization goes through too, rejects them, and the
+// `update` methods taking bare f64 values as well as `IchimokuCloud::calculate` skip them
+// by returning None with their state untouched. The guard adds the choice to reject,
+// and covers candles whose sums overflowed, e.g. aggregated volumes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFinitePolicy {
+    // Drop the candle as if it never arrived: no result, the state is untouched.
+    #[default]
+    Skip,
+    // Drop the candle like Skip, but report it as an error so the caller notices.
+    Reject,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NonFiniteInput;
+
+impl fmt::Display for NonFiniteInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "candle has a non-finite price or volume")
+    }
+}
+
+impl core::error::Error for NonFiniteInput {}
+
+// Keeps non-finite candles away from an indicator, following its `NonFinitePolicy`.
+pub struct FiniteGuard<I> {
+    inner: I,
+    policy: NonFinitePolicy,
+}
+
+impl<I: Indicator> FiniteGuard<I> {
+    pub fn new(inner: I, policy: NonFinitePolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    // Pass a finite candle on to the indicator. A non-finite one never reaches it and
+    // gives Ok(None) when skipped or Err when rejected.
+    pub fn update(&mut self, candle: &Candlestick) -> Result<Option<I::Output>, NonFiniteInput> {
+        if candle.is_finite() {
+            return Ok(self.inner.update(candle));
+        }
+        match self.policy {
+            NonFinitePolicy::Skip => Ok(None),
+            NonFinitePolicy::Reject => Err(NonFiniteInput),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+// Through the trait a rejected candle can't be reported, it gives None like a skipped one.
+impl<I: Indicator> Indicator for FiniteGuard<I> {
+    type Output = I::Output;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<I::Output> {
+        FiniteGuard::update(self, candle).ok().flatten()
+    }
+
+    fn reset(&mut self) {
+        FiniteGuard::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.inner.bars_until_ready()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::candlestick::{CandlestickState, TimeFrame};
+    use crate::sma::SimpleMovingAverage;
+
+    fn candle(close: f64) -> Candlestick {
+        Candlestick {
+            open: close,
+            high: close,
+            low: close,
+            close,
+            time_frame: TimeFrame::OneDay,
+            timestamp: None,
+            number_of_trades: 1,
+            volume: None,
+            state: CandlestickState::Closed,
+            synthetic: false,
+        }
+    }
+
+    #[test]
+    fn keeps_non_finite_candles_away() {
+        let mut skipping = FiniteGuard::new(SimpleMovingAverage::new(2), NonFinitePolicy::Skip);
+        let mut rejecting = FiniteGuard::new(SimpleMovingAverage::new(2), NonFinitePolicy::Reject);
+        for guard in [&mut skipping, &mut rejecting] {
+            assert_eq!(guard.update(&candle(1.0)), Ok(None));
+        }
+
+        assert_eq!(skipping.update(&candle(f64::NAN)), Ok(None));
+        assert_eq!(rejecting.update(&candle(f64::NAN)), Err(NonFiniteInput));
+        let infinite_volume = Candlestick {
+            volume: Some(f64::INFINITY),
+            ..candle(2.0)
+        };
+        assert_eq!(rejecting.update(&infinite_volume), Err(NonFiniteInput));
+
+        for guard in [&mut skipping, &mut rejecting] {
+            assert_eq!(guard.update(&candle(3.0)), Ok(Some(2.0)));
+            assert_eq!(Indicator::update(guard, &candle(f64::NAN)), None);
+            assert_eq!(guard.bars_until_ready(), 0);
+        }
+    }
+}
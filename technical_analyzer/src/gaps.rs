@@ -0,0 +1,223 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::{Candlestick, CandlestickState};
+use crate::utils::round_to;
+
+// Indices of the candles that aren't one bar after the previous timestamped candle, with
+// the number of bars missing in between. The bar is measured from the previous timestamp
+// with `TimeFrame::shift`, so the bars don't have to start on the epoch grid, e.g. daily
+// bars stamped at the 14:30 UTC open, and months follow the calendar. Candles closer than
+// a bar, or out of order, show up with a count of 0 or below. Candles without a timestamp
+// are skipped.
+pub fn find_gaps(candles: &[Candlestick]) -> Vec<(usize, i64)> {
+    let mut gaps = Vec::new();
+    let mut previous: Option<&Candlestick> = None;
+
+    for (index, candle) in candles.iter().enumerate() {
+        let Some(timestamp) = candle.timestamp else {
+            continue;
+        };
+        if let Some(previous) = previous {
+            let time_frame = previous.time_frame;
+            let expected = time_frame.shift(previous.timestamp.unwrap_or(timestamp), 1);
+            if timestamp != expected {
+                gaps.push((index, time_frame.bars_between(expected, timestamp)));
+            }
+        }
+        previous = Some(candle);
+    }
+
+    gaps
+}
+
+// The candles with a flat synthetic candle inserted for every bar missing between two
+// timestamped candles, so indicators see a contiguous series. Missing bars are counted
+// from the previous timestamp like in `find_gaps`, and fillers keep its time of day. A
+// filler candle opens, closes and ranges at the close before it, has no trades, a volume
+// of 0 if the candle before it had a volume, and is Closed and marked with `is_synthetic`.
+pub fn fill_gaps(candles: &[Candlestick]) -> Vec<Candlestick> {
+    let mut filled: Vec<Candlestick> = Vec::with_capacity(candles.len());
+    let mut previous: Option<&Candlestick> = None;
+
+    for candle in candles {
+        let Some(timestamp) = candle.timestamp else {
+            filled.push(candle.clone());
+            continue;
+        };
+        if let Some(previous) = previous {
+            let time_frame = previous.time_frame;
+            let start = previous.timestamp.unwrap_or(timestamp);
+            // Shifting from the start each time keeps month ends from drifting
+            let mut bars = 1;
+            let mut missing = time_frame.shift(start, bars);
+            while missing < timestamp {
+                filled.push(Candlestick {
+                    open: previous.close,
+                    close: previous.close,
+                    high: previous.close,
+                    low: previous.close,
+                    time_frame: previous.time_frame,
+                    timestamp: Some(missing),
+                    number_of_trades: 0,
+                    volume: previous.volume.map(|_| 0.0),
+                    state: CandlestickState::Closed,
+                    synthetic: true,
+                });
+                bars += 1;
+                missing = time_frame.shift(start, bars);
+            }
+        }
+        filled.push(candle.clone());
+        previous = Some(candle);
+    }
+
+    filled
+}
+
+// A price gap between two consecutive candles, as opposed to the missing bars above. The
+// size is the distance from the previous candle's high or low to the current open, always
+// positive. Dividing it by the ATR makes gaps comparable across instruments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GapKind {
+    // The current candle opened above the previous high
+    Up(f64),
+    // The current candle opened below the previous low
+    Down(f64),
+    None,
+}
+
+// Whether `curr` opened outside the range of `prev`. An open exactly at the previous high
+// or low isn't a gap.
+pub fn gap(prev: &Candlestick, curr: &Candlestick) -> GapKind {
+    if curr.open > prev.high {
+        GapKind::Up(round_to(curr.open - prev.high, 8))
+    } else if curr.open < prev.low {
+        GapKind::Down(round_to(prev.low - curr.open, 8))
+    } else {
+        GapKind::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::candlestick::TimeFrame;
+    use alloc::vec;
+
+    fn candle(time_frame: TimeFrame, timestamp: i64, close: f64) -> Candlestick {
+        Candlestick::new(
+            close,
+            close,
+            close,
+            close,
+            time_frame,
+            Some(timestamp),
+            3,
+            Some(crate::units::Volume::from(5.0)),
+            CandlestickState::Closed,
+        )
+        .unwrap()
+    }
+
+    fn minutes(timestamps: &[i64]) -> Vec<Candlestick> {
+        timestamps
+            .iter()
+            .map(|&t| candle(TimeFrame::OneMinute, t, 1.0 + t as f64))
+            .collect()
+    }
+
+    // 2024-01-01, 2024-02-01, 2024-03-01 and 2024-04-01 at UTC midnight
+    const MONTHS: [i64; 4] = [1_704_067_200, 1_706_745_600, 1_709_251_200, 1_711_929_600];
+
+    #[test]
+    fn finds_missing_and_misplaced_bars() {
+        let candles = minutes(&[0, 60, 180, 240, 240, 600]);
+        assert_eq!(find_gaps(&candles), vec![(2, 1), (4, -1), (5, 5)]);
+    }
+
+    #[test]
+    fn bars_off_the_epoch_grid() {
+        // Daily bars at 14:30 UTC, with the fourth day missing
+        let open = 14 * 3600 + 30 * 60;
+        let day = TimeFrame::OneDay.seconds();
+        let candles: Vec<_> = [0, 1, 2, 4]
+            .iter()
+            .map(|&i| candle(TimeFrame::OneDay, open + i * day, 1.0))
+            .collect();
+        assert_eq!(find_gaps(&candles), vec![(3, 1)]);
+        assert!(find_gaps(&candles[..3]).is_empty());
+
+        let filled = fill_gaps(&candles);
+        assert_eq!(filled.len(), 5);
+        assert!(filled[3].is_synthetic());
+        assert_eq!(filled[3].timestamp, Some(open + 3 * day));
+        assert_eq!(fill_gaps(&candles[..3]), candles[..3]);
+    }
+
+    #[test]
+    fn fills_missing_bars_with_flat_candles() {
+        let filled = fill_gaps(&minutes(&[0, 60, 240]));
+        assert_eq!(filled.len(), 5);
+        assert!(find_gaps(&filled).is_empty());
+
+        let filler = &filled[2];
+        assert!(filler.is_synthetic());
+        assert_eq!(filler.timestamp, Some(120));
+        assert_eq!(
+            (filler.open, filler.high, filler.low, filler.close),
+            (61.0, 61.0, 61.0, 61.0)
+        );
+        assert_eq!(filler.number_of_trades, 0);
+        assert_eq!(filler.volume, Some(0.0));
+        assert_eq!(filled[3].timestamp, Some(180));
+        assert!(!filled[4].is_synthetic());
+    }
+
+    #[test]
+    fn months_follow_the_calendar() {
+        let candles: Vec<_> = MONTHS
+            .iter()
+            .map(|&t| candle(TimeFrame::OneMonth, t, 1.0))
+            .collect();
+        assert!(find_gaps(&candles).is_empty());
+        assert_eq!(fill_gaps(&candles), candles);
+
+        let skipped = [candles[0].clone(), candles[3].clone()];
+        assert_eq!(find_gaps(&skipped), vec![(1, 2)]);
+        let filled: Vec<_> = fill_gaps(&skipped).iter().map(|c| c.timestamp).collect();
+        assert_eq!(filled, MONTHS.map(Some));
+    }
+
+    #[test]
+    fn price_gaps() {
+        let previous = Candlestick::new(
+            10.0,
+            12.0,
+            9.0,
+            11.0,
+            TimeFrame::OneDay,
+            None,
+            1,
+            None,
+            CandlestickState::Closed,
+        )
+        .unwrap();
+        let opening_at = |open: f64| {
+            Candlestick::new(
+                open,
+                open,
+                open,
+                open,
+                TimeFrame::OneDay,
+                None,
+                1,
+                None,
+                CandlestickState::Closed,
+            )
+            .unwrap()
+        };
+        assert_eq!(gap(&previous, &opening_at(12.5)), GapKind::Up(0.5));
+        assert_eq!(gap(&previous, &opening_at(8.25)), GapKind::Down(0.75));
+        assert_eq!(gap(&previous, &opening_at(12.0)), GapKind::None);
+    }
+}
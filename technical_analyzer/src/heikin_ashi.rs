@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+use crate::ichimoku::{IchimokuCloud, IchimokuCloudParameters, IchimokuCloudResult};
+
+// Transform candles into Heikin-Ashi candles. The close is the average of the raw OHLC,
+// the open the midpoint of the previous Heikin-Ashi open and close, and the high and low
+// extend the raw extremes to cover both. The first open is the midpoint of the first raw
+// candle's open and close. Everything else is copied from the raw candles.
+pub fn heikin_ashi(candles: &[Candlestick]) -> Vec<Candlestick> {
+    let mut transformed: Vec<Candlestick> = Vec::with_capacity(candles.len());
+
+    for candle in candles {
+        let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+        let open = match transformed.last() {
+            Some(previous) => (previous.open + previous.close) / 2.0,
+            None => (candle.open + candle.close) / 2.0,
+        };
+
+        transformed.push(Candlestick {
+            open,
+            close,
+            high: candle.high.max(open).max(close),
+            low: candle.low.min(open).min(close),
+            time_frame: candle.time_frame,
+            timestamp: candle.timestamp,
+            number_of_trades: candle.number_of_trades,
+            volume: candle.volume,
+            state: candle.state,
+            synthetic: candle.synthetic,
+        });
+    }
+
+    transformed
+}
+
+// Run a fresh Ichimoku cloud over the Heikin-Ashi transform of the candles, pairing each
+// Heikin-Ashi candle with its result. Heikin-Ashi candles average every bar with the one
+// before, so the lines react a bar or so later to a turn than on raw candles, but a single
+// spike moves them less and the Tenkan/Kijun crosses that whipsaw on noisy raw data mostly
+// disappear. The Chikou span is the Heikin-Ashi close, not the traded close.
+pub fn ichimoku_heikin_ashi(
+    candles: &[Candlestick],
+    parameters: IchimokuCloudParameters,
+) -> Vec<(Candlestick, Option<IchimokuCloudResult>)> {
+    let mut cloud = IchimokuCloud::new(parameters);
+    heikin_ashi(candles)
+        .into_iter()
+        .map(|candle| {
+            let result = cloud.calculate(&candle);
+            (candle, result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn averages_each_candle_with_the_one_before() {
+        let candles = [
+            Candlestick::ohlc(10.0, 12.0, 8.0, 11.0),
+            Candlestick::ohlc(11.0, 13.0, 10.0, 12.0),
+            Candlestick::ohlc(12.0, 12.5, 11.5, 12.0),
+        ];
+        let transformed = heikin_ashi(&candles);
+        let ohlc: Vec<_> = transformed
+            .iter()
+            .map(|c| (c.open, c.high, c.low, c.close))
+            .collect();
+        assert_eq!(
+            ohlc,
+            [
+                (10.5, 12.0, 8.0, 10.25),
+                (10.375, 13.0, 10.0, 11.5),
+                (10.9375, 12.5, 10.9375, 12.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn cloud_runs_on_the_transformed_candles() {
+        let candles: Vec<_> = (0..4)
+            .map(|i| {
+                let price = 10.0 + i as f64;
+                Candlestick::ohlc(price, price + 2.0, price - 2.0, price)
+            })
+            .collect();
+        let parameters = IchimokuCloudParameters::new(1, 2, 3).unwrap();
+        let pairs = ichimoku_heikin_ashi(&candles, parameters);
+
+        let mut cloud = IchimokuCloud::new(parameters);
+        for ((candle, result), expected) in pairs.iter().zip(heikin_ashi(&candles)) {
+            assert_eq!(candle.open, expected.open);
+            assert_eq!(result, &cloud.calculate(&expected));
+        }
+    }
+
+    #[test]
+    fn heikin_ashi_crosses_later_on_a_turn() {
+        use crate::ichimoku::Cross;
+
+        // A steady fall from 120 to 98 then a slower climb, each candle opening at the
+        // previous close
+        let closes: Vec<f64> = (0..12)
+            .map(|i| 120.0 - 2.0 * i as f64)
+            .chain((1..=12).map(|i| 98.0 + i as f64))
+            .collect();
+        let candles: Vec<_> = closes
+            .iter()
+            .zip(closes.iter().skip(1))
+            .map(|(&open, &close)| {
+                Candlestick::ohlc(open, open.max(close) + 0.5, open.min(close) - 0.5, close)
+            })
+            .collect();
+        let parameters = IchimokuCloudParameters::new(2, 4, 5).unwrap();
+        let first_bullish_cross = |results: Vec<Option<IchimokuCloudResult>>| {
+            results
+                .windows(2)
+                .position(|pair| match pair {
+                    [Some(previous), Some(current)] => {
+                        IchimokuCloud::cross(previous, current) == Cross::BullishTk
+                    }
+                    _ => false,
+                })
+                .map(|index| index + 1)
+        };
+
+        let mut cloud = IchimokuCloud::new(parameters);
+        let raw = first_bullish_cross(candles.iter().map(|c| cloud.calculate(c)).collect());
+        let smoothed = first_bullish_cross(
+            ichimoku_heikin_ashi(&candles, parameters)
+                .into_iter()
+                .map(|(_, result)| result)
+                .collect(),
+        );
+        // The Heikin-Ashi opens still carry the fall, delaying the cross by a bar
+        assert_eq!((raw, smoothed), (Some(13), Some(14)));
+    }
+}
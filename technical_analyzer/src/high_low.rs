@@ -0,0 +1,72 @@
+use crate::rolling_extrema::RollingExtrema;
+
+// Highs and lows of the last `period` candles, for indicators working off the highest
+// high and lowest low of a window.
+pub(crate) struct HighLowWindow {
+    highs: RollingExtrema,
+    lows: RollingExtrema,
+}
+
+impl HighLowWindow {
+    pub(crate) fn new(period: usize) -> Self {
+        Self {
+            highs: RollingExtrema::new(period),
+            lows: RollingExtrema::new(period),
+        }
+    }
+
+    pub(crate) fn push(&mut self, high: f64, low: f64) {
+        self.highs.push(high);
+        self.lows.push(low);
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.highs.is_full()
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.highs.remaining()
+    }
+
+    pub(crate) fn highest(&self) -> f64 {
+        self.highs.max().unwrap_or(f64::MIN)
+    }
+
+    pub(crate) fn lowest(&self) -> f64 {
+        self.lows.min().unwrap_or(f64::MAX)
+    }
+
+    // The highest high and lowest low if a candle with this high and low was pushed.
+    pub(crate) fn extremes_with(&self, high: f64, low: f64) -> (f64, f64) {
+        (self.highs.max_with(high), self.lows.min_with(low))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.highs.clear();
+        self.lows.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_the_extremes_of_the_window() {
+        let mut window = HighLowWindow::new(2);
+        assert_eq!(window.remaining(), 2);
+        window.push(5.0, 3.0);
+        assert!(!window.is_full());
+        window.push(4.0, 1.0);
+        assert!(window.is_full());
+        assert_eq!((window.highest(), window.lowest()), (5.0, 1.0));
+        assert_eq!(window.extremes_with(6.0, 2.0), (6.0, 1.0));
+
+        // The first candle leaves the window
+        window.push(4.5, 2.0);
+        assert_eq!((window.highest(), window.lowest()), (4.5, 1.0));
+
+        window.clear();
+        assert_eq!(window.remaining(), 2);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::{chained, Indicator};
+use crate::units::Price;
+use crate::wma::WeightedMovingAverage;
+
+// Hull moving average: the WMA over the square root of `period` bars of
+// 2 * WMA(period / 2) - WMA(period). Doubling the faster average cancels most of the lag
+// of the slower one, and the short final WMA smooths the result.
+pub struct HullMovingAverage {
+    half: WeightedMovingAverage,
+    full: WeightedMovingAverage,
+    smoothing: WeightedMovingAverage,
+}
+
+impl HullMovingAverage {
+    // The half and square root periods are rounded down, but at least 1. Panics if
+    // `period` is 0.
+    pub fn new(period: usize) -> Self {
+        Self {
+            half: WeightedMovingAverage::new((period / 2).max(1)),
+            full: WeightedMovingAverage::new(period),
+            smoothing: WeightedMovingAverage::new(period.isqrt().max(1)),
+        }
+    }
+
+    // Returns None until `period + sqrt(period) - 1` closes have been seen.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let half = self.half.update(close);
+        let full = self.full.update(close)?;
+        self.smoothing.update(2.0 * half? - full)
+    }
+
+    pub fn reset(&mut self) {
+        self.half.reset();
+        self.full.reset();
+        self.smoothing.reset();
+    }
+}
+
+impl Indicator for HullMovingAverage {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        HullMovingAverage::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        HullMovingAverage::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        chained(
+            self.half
+                .bars_until_ready()
+                .max(self.full.bars_until_ready()),
+            self.smoothing.bars_until_ready(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_a_ramp_without_lag() {
+        let mut hull = HullMovingAverage::new(4);
+        assert_eq!(Indicator::bars_until_ready(&hull), 5);
+        for close in 1..5 {
+            assert_eq!(hull.update(close as f64), None);
+        }
+        // The averages are rounded on the way, so only to within their precision
+        for close in 5..8 {
+            let close = close as f64;
+            assert!((hull.update(close).unwrap() - close).abs() < 1e-7);
+        }
+
+        hull.reset();
+        assert_eq!(Indicator::bars_until_ready(&hull), 5);
+    }
+}
@@ -0,0 +1,910 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::candlestick::{Candlestick, CandlestickState};
+use crate::ema::ExponentialMovingAverage;
+use crate::high_low::HighLowWindow;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+// Built through `new` or `Default`, deserializing checks the periods the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawParameters"))]
+pub struct IchimokuCloudParameters {
+    short_period: usize,
+    medium_period: usize,
+    long_period: usize,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawParameters {
+    short_period: usize,
+    medium_period: usize,
+    long_period: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawParameters> for IchimokuCloudParameters {
+    type Error = ParamError;
+
+    fn try_from(raw: RawParameters) -> Result<Self, ParamError> {
+        Self::new(raw.short_period, raw.medium_period, raw.long_period)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParamError {
+    ZeroPeriod,
+    // The periods must grow strictly from short to medium to long
+    PeriodsOutOfOrder,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamError::ZeroPeriod => write!(f, "periods must be at least 1"),
+            ParamError::PeriodsOutOfOrder => {
+                write!(f, "periods must satisfy short < medium < long")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParamError {}
+
+impl IchimokuCloudParameters {
+    // Parameters checked for 0 < short < medium < long.
+    pub fn new(
+        short_period: usize,
+        medium_period: usize,
+        long_period: usize,
+    ) -> Result<Self, ParamError> {
+        if short_period == 0 {
+            return Err(ParamError::ZeroPeriod);
+        }
+        if short_period >= medium_period || medium_period >= long_period {
+            return Err(ParamError::PeriodsOutOfOrder);
+        }
+        Ok(Self {
+            short_period,
+            medium_period,
+            long_period,
+        })
+    }
+
+    // Tenkan-sen period
+    pub fn short_period(&self) -> usize {
+        self.short_period
+    }
+
+    // Kijun-sen period, and the default displacement
+    pub fn medium_period(&self) -> usize {
+        self.medium_period
+    }
+
+    // Senkou span B period
+    pub fn long_period(&self) -> usize {
+        self.long_period
+    }
+}
+
+impl Default for IchimokuCloudParameters {
+    // The canonical 9/26/52 configuration.
+    fn default() -> Self {
+        Self {
+            short_period: 9,
+            medium_period: 26,
+            long_period: 52,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IchimokuCloudResult {
+    pub tenkan_sen: f64,
+    pub kijun_sen: f64,
+    // The leading spans are computed on the current candle but belong `displacement`
+    // bars into the future, at `projected_timestamp`, see `IchimokuCloud::projected_cloud`.
+    pub senkou_span_a: f64,
+    pub senkou_span_b: f64,
+    // The candle's timestamp moved `displacement` bars ahead, None without a timestamp
+    pub projected_timestamp: Option<i64>,
+    // The leading spans computed `displacement` results earlier, which form the cloud
+    // under the current candle. None until that many results have been seen.
+    pub current_span_a: Option<f64>,
+    pub current_span_b: Option<f64>,
+    // The lagging span is the current close plotted `displacement` bars back,
+    // see `IchimokuCloud::chikou_vs_price`.
+    pub chikou_span: f64,
+}
+
+// All five values on one line, to 8 decimals.
+impl fmt::Display for IchimokuCloudResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tenkan_sen: {:.8}, kijun_sen: {:.8}, senkou_span_a: {:.8}, senkou_span_b: {:.8}, chikou_span: {:.8}",
+            self.tenkan_sen, self.kijun_sen, self.senkou_span_a, self.senkou_span_b, self.chikou_span
+        )
+    }
+}
+
+// How Tenkan-sen and Kijun-sen are computed. Classic takes the midpoint of the highest
+// high and lowest low over the short and medium periods, Ema smooths the midprice,
+// (high + low) / 2, with EMAs of those periods instead. Senkou span B is the midpoint of
+// the long period either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IchimokuMode {
+    #[default]
+    Classic,
+    Ema,
+}
+
+// Trading signal derived from the position of the price relative to the cloud.
+//
+// - StrongBull: the close is above a green cloud (Senkou A above Senkou B), Tenkan-sen is
+//   above Kijun-sen and the Chikou span is above the price it is plotted against.
+// - WeakBull: the close is above the cloud but not every other condition holds.
+// - StrongBear / WeakBear: the mirror image below a red cloud.
+// - Neutral: the close is inside the cloud, or there isn't enough data yet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IchimokuSignal {
+    StrongBull,
+    WeakBull,
+    Neutral,
+    WeakBear,
+    StrongBear,
+}
+
+// Tenkan-sen crossing Kijun-sen between two consecutive results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cross {
+    BullishTk,
+    BearishTk,
+    None,
+}
+
+pub struct IchimokuCloud {
+    // Highs and lows of the most recent closed candles over the short, medium and long
+    // periods.
+    tenkan_window: HighLowWindow,
+    kijun_window: HighLowWindow,
+    senkou_window: HighLowWindow,
+    // Midprice averages for `IchimokuMode::Ema`, fed alongside the windows
+    tenkan_ema: ExponentialMovingAverage,
+    kijun_ema: ExponentialMovingAverage,
+    mode: IchimokuMode,
+    // Decimal places the results are rounded to
+    precision: u32,
+    // Leading spans of the last `displacement + 1` closed candles, keyed by the timestamp
+    // they are plotted at. The oldest one is the cloud under the last closed candle.
+    projected_spans: VecDeque<(Option<i64>, f64, f64)>,
+    // Closes of the last `displacement + 1` closed candles, oldest first.
+    closes: VecDeque<f64>,
+    displacement: usize,
+    last_result: Option<IchimokuCloudResult>,
+    parameters: IchimokuCloudParameters,
+    // Closed candles committed to the windows, by `initialize`, `warm_up` or `calculate`
+    num_processed: usize,
+}
+
+impl IchimokuCloud {
+    pub fn new(params: IchimokuCloudParameters) -> Self {
+        Self {
+            tenkan_window: HighLowWindow::new(params.short_period),
+            kijun_window: HighLowWindow::new(params.medium_period),
+            senkou_window: HighLowWindow::new(params.long_period),
+            tenkan_ema: ExponentialMovingAverage::new_seeded(params.short_period),
+            kijun_ema: ExponentialMovingAverage::new_seeded(params.medium_period),
+            mode: IchimokuMode::Classic,
+            precision: 8,
+            projected_spans: VecDeque::with_capacity(params.medium_period + 2),
+            closes: VecDeque::with_capacity(params.medium_period + 2),
+            displacement: params.medium_period,
+            last_result: None,
+            parameters: params,
+            num_processed: 0,
+        }
+    }
+
+    // Every closed candle is counted once, whichever method committed it, so warming up
+    // on history and then streaming counts each candle once.
+    pub fn num_processed(&self) -> usize {
+        self.num_processed
+    }
+
+    // Closed candles still needed before `calculate` returns the first result, counting
+    // the one that does. An Open candle gets provisional lines from the last of them on,
+    // but doesn't count down.
+    pub fn bars_until_ready(&self) -> usize {
+        self.parameters
+            .long_period
+            .saturating_sub(self.num_processed)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.bars_until_ready() == 0
+    }
+
+    // Forget every candle seen so far so the cloud can be reused, e.g. for another symbol.
+    // The parameters and displacement are kept.
+    pub fn reset(&mut self) {
+        self.tenkan_window.clear();
+        self.kijun_window.clear();
+        self.senkou_window.clear();
+        self.tenkan_ema.reset();
+        self.kijun_ema.reset();
+        self.projected_spans.clear();
+        self.closes.clear();
+        self.last_result = None;
+        self.num_processed = 0;
+    }
+
+    // Plot the leading spans this many bars ahead instead of the default `medium_period`.
+    pub fn with_displacement(mut self, displacement: usize) -> Self {
+        self.displacement = displacement;
+        self
+    }
+
+    // Compute Tenkan-sen and Kijun-sen as in `mode`, Classic by default.
+    pub fn with_mode(mut self, mode: IchimokuMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    // Round the results, and the projected cloud built from them, to this many decimal
+    // places instead of 8, e.g. 2 for equities quoted in cents.
+    pub fn with_precision(mut self, decimals: u32) -> Self {
+        self.precision = decimals;
+        self
+    }
+
+    // Where the lagging span of the last closed candle sits relative to the candle it is
+    // plotted against, `displacement` bars ago. None until enough closes have been seen.
+    pub fn chikou_vs_price(&self) -> Option<Ordering> {
+        if self.closes.len() <= self.displacement {
+            return None;
+        }
+        let current = self.closes.back()?;
+        let past = self.closes.front()?;
+        current.partial_cmp(past)
+    }
+
+    // The cloud ahead of the last closed candle as (timestamp, senkou span a, senkou span b),
+    // oldest first. Candles without a timestamp can't be projected and are left out.
+    pub fn projected_cloud(&self) -> Vec<(i64, f64, f64)> {
+        let current = self.projected_spans.len().saturating_sub(self.displacement);
+        self.projected_spans
+            .iter()
+            .skip(current)
+            .filter_map(|&(timestamp, span_a, span_b)| timestamp.map(|t| (t, span_a, span_b)))
+            .collect()
+    }
+
+    // Senkou span a and b of the cloud under the last closed candle.
+    fn current_cloud(&self) -> Option<(f64, f64)> {
+        if self.projected_spans.len() <= self.displacement {
+            return None;
+        }
+        self.projected_spans
+            .front()
+            .map(|&(_, span_a, span_b)| (span_a, span_b))
+    }
+
+    // Classify the candle's close against the current cloud and the last closed result,
+    // following the rules documented on `IchimokuSignal`.
+    pub fn signal(&self, candle: &Candlestick) -> IchimokuSignal {
+        let (Some(result), Some((span_a, span_b))) = (&self.last_result, self.current_cloud())
+        else {
+            return IchimokuSignal::Neutral;
+        };
+        let chikou = self.chikou_vs_price();
+
+        if candle.close > span_a.max(span_b) {
+            if span_a > span_b
+                && result.tenkan_sen > result.kijun_sen
+                && chikou == Some(Ordering::Greater)
+            {
+                IchimokuSignal::StrongBull
+            } else {
+                IchimokuSignal::WeakBull
+            }
+        } else if candle.close < span_a.min(span_b) {
+            if span_a < span_b
+                && result.tenkan_sen < result.kijun_sen
+                && chikou == Some(Ordering::Less)
+            {
+                IchimokuSignal::StrongBear
+            } else {
+                IchimokuSignal::WeakBear
+            }
+        } else {
+            IchimokuSignal::Neutral
+        }
+    }
+
+    // Warm up from history, pairing every candle with its result. Candles are handled like
+    // in `calculate`: an Open candle, usually the last one, is computed but not committed,
+    // so it isn't counted twice when it arrives again once it closed.
+    pub fn initialize<'a>(
+        &mut self,
+        candlesticks: &'a [Candlestick],
+    ) -> Vec<(&'a Candlestick, Option<IchimokuCloudResult>)> {
+        self.iter_ichimoku(candlesticks).collect()
+    }
+
+    // The lazy version of `initialize`: each candle goes through `calculate` only when the
+    // iterator reaches it, so long histories can be streamed without collecting them.
+    pub fn iter_ichimoku<'a, 's>(
+        &'s mut self,
+        candles: &'a [Candlestick],
+    ) -> impl Iterator<Item = (&'a Candlestick, Option<IchimokuCloudResult>)> + 's
+    where
+        'a: 's,
+    {
+        candles
+            .iter()
+            .map(move |candle| (candle, self.calculate(candle)))
+    }
+
+    // Feed candles through `calculate` and return only the result of the last one, for
+    // catching up on history before streaming without collecting every result. An Open
+    // candle at the end is computed but not committed, like in `calculate`.
+    pub fn warm_up(&mut self, candles: &[Candlestick]) -> Option<IchimokuCloudResult> {
+        candles
+            .iter()
+            .fold(None, |_, candle| self.calculate(candle))
+    }
+
+    // Calculate the Ichimoku Cloud values for a given candlestick. This is the live path,
+    // call it for every update after warming up with `initialize` or `warm_up`.
+    // If the candlestick is closed, also update the state. A candle with a non-finite price
+    // or volume is skipped, giving None.
+    pub fn calculate(&mut self, candle: &Candlestick) -> Option<IchimokuCloudResult> {
+        if !candle.is_finite() {
+            return None;
+        }
+        match candle.state {
+            // If the candlestick is closed, update the state
+            CandlestickState::Closed => self.commit(candle),
+            // An open candle must not stay in the windows, it can still change
+            CandlestickState::Open => self.peek(candle),
+        }
+    }
+
+    // The provisional lines as if `candle` were the next one, without taking it in, e.g.
+    // to show live values while a candle is still forming. This is what `calculate`
+    // returns for an Open candle. A Closed candle is treated the same way, it is only
+    // committed by `calculate`.
+    pub fn peek(&self, candle: &Candlestick) -> Option<IchimokuCloudResult> {
+        if !candle.is_finite() {
+            return None;
+        }
+        self.compute(candle, true)
+    }
+
+    // Same as `calculate`, also reporting whether Tenkan-sen crossed Kijun-sen since the
+    // last closed candle.
+    pub fn calculate_with_cross(
+        &mut self,
+        candle: &Candlestick,
+    ) -> Option<(IchimokuCloudResult, Cross)> {
+        let previous = self.last_result.clone();
+        let result = self.calculate(candle)?;
+        let cross = match previous {
+            Some(previous) => Self::cross(&previous, &result),
+            None => Cross::None,
+        };
+        Some((result, cross))
+    }
+
+    // The cross event between a previous and a current result. Touching without crossing
+    // doesn't count, the Tenkan-sen has to end up strictly on the other side.
+    pub fn cross(previous: &IchimokuCloudResult, current: &IchimokuCloudResult) -> Cross {
+        if previous.tenkan_sen <= previous.kijun_sen && current.tenkan_sen > current.kijun_sen {
+            Cross::BullishTk
+        } else if previous.tenkan_sen >= previous.kijun_sen
+            && current.tenkan_sen < current.kijun_sen
+        {
+            Cross::BearishTk
+        } else {
+            Cross::None
+        }
+    }
+
+    // Add a candle to the windows for good and compute its lines.
+    fn commit(&mut self, candle: &Candlestick) -> Option<IchimokuCloudResult> {
+        self.num_processed += 1;
+        self.push(candle);
+        let result = self.compute(candle, false);
+        self.record_close(candle);
+        self.project(&result);
+        result
+    }
+
+    fn push(&mut self, candle: &Candlestick) {
+        self.tenkan_window.push(candle.high, candle.low);
+        self.kijun_window.push(candle.high, candle.low);
+        self.senkou_window.push(candle.high, candle.low);
+        if self.mode == IchimokuMode::Ema {
+            let midprice = halfway(candle.high, candle.low);
+            self.tenkan_ema.update(midprice);
+            self.kijun_ema.update(midprice);
+        }
+    }
+
+    fn record_close(&mut self, candle: &Candlestick) {
+        self.closes.push_back(candle.close);
+        while self.closes.len() > self.displacement + 1 {
+            self.closes.pop_front();
+        }
+    }
+
+    // Remember the result of a committed candle and its leading spans at the timestamp
+    // they belong to.
+    fn project(&mut self, result: &Option<IchimokuCloudResult>) {
+        self.last_result = result.clone();
+        if let Some(result) = result {
+            self.projected_spans.push_back((
+                result.projected_timestamp,
+                result.senkou_span_a,
+                result.senkou_span_b,
+            ));
+            while self.projected_spans.len() > self.displacement + 1 {
+                self.projected_spans.pop_front();
+            }
+        }
+    }
+
+    // Midpoint of the highest high and lowest low in the window. A pending candle that
+    // hasn't been pushed yet takes part as the newest candle.
+    fn midpoint(window: &HighLowWindow, pending: Option<&Candlestick>) -> f64 {
+        let (high, low) = match pending {
+            Some(candle) => window.extremes_with(candle.high, candle.low),
+            None => (window.highest(), window.lowest()),
+        };
+        halfway(high, low)
+    }
+
+    // The lines for a candle that was just pushed, or for a pending one that wasn't.
+    // A pending candle counts towards the warm-up as if it had been committed, so peeking
+    // at a candle gives the same lines as committing it.
+    fn compute(&self, candle: &Candlestick, pending: bool) -> Option<IchimokuCloudResult> {
+        if self.num_processed + usize::from(pending) < self.parameters.long_period {
+            return None;
+        }
+
+        let pending = pending.then_some(candle);
+        let (tenkan_sen, kijun_sen) = match (self.mode, pending) {
+            (IchimokuMode::Classic, _) => (
+                Self::midpoint(&self.tenkan_window, pending),
+                Self::midpoint(&self.kijun_window, pending),
+            ),
+            (IchimokuMode::Ema, Some(pending)) => {
+                let midprice = halfway(pending.high, pending.low);
+                (
+                    self.tenkan_ema.peek(midprice),
+                    self.kijun_ema.peek(midprice),
+                )
+            }
+            (IchimokuMode::Ema, None) => (self.tenkan_ema.value()?, self.kijun_ema.value()?),
+        };
+        let senkou_span_a = halfway(tenkan_sen, kijun_sen);
+        let senkou_span_b = Self::midpoint(&self.senkou_window, pending);
+        let chikou_span = candle.close;
+        // The candle isn't among the projected spans yet, whether pending or not
+        let current_cloud = self
+            .projected_spans
+            .len()
+            .checked_sub(self.displacement)
+            .and_then(|index| self.projected_spans.get(index));
+
+        Some(IchimokuCloudResult {
+            tenkan_sen: round_to(tenkan_sen, self.precision),
+            kijun_sen: round_to(kijun_sen, self.precision),
+            senkou_span_a: round_to(senkou_span_a, self.precision),
+            senkou_span_b: round_to(senkou_span_b, self.precision),
+            chikou_span: round_to(chikou_span, self.precision),
+            projected_timestamp: candle
+                .timestamp
+                .map(|t| candle.time_frame.shift(t, self.displacement as i64)),
+            current_span_a: current_cloud.map(|&(_, span_a, _)| span_a),
+            current_span_b: current_cloud.map(|&(_, _, span_b)| span_b),
+        })
+    }
+}
+
+// The midpoint of two prices. Halving first can't overflow and gives the same result as
+// halving the sum.
+fn halfway(a: f64, b: f64) -> f64 {
+    a / 2.0 + b / 2.0
+}
+
+impl Indicator for IchimokuCloud {
+    type Output = IchimokuCloudResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<IchimokuCloudResult> {
+        self.calculate(candle)
+    }
+
+    fn reset(&mut self) {
+        IchimokuCloud::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        IchimokuCloud::bars_until_ready(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn parameters_are_validated() {
+        assert_eq!(
+            IchimokuCloudParameters::new(0, 26, 52),
+            Err(ParamError::ZeroPeriod)
+        );
+        assert_eq!(
+            IchimokuCloudParameters::new(26, 9, 52),
+            Err(ParamError::PeriodsOutOfOrder)
+        );
+        assert_eq!(
+            IchimokuCloudParameters::new(9, 26, 26),
+            Err(ParamError::PeriodsOutOfOrder)
+        );
+        let parameters = IchimokuCloudParameters::new(9, 26, 52).unwrap();
+        assert_eq!(parameters, IchimokuCloudParameters::default());
+        assert_eq!(
+            (
+                parameters.short_period(),
+                parameters.medium_period(),
+                parameters.long_period()
+            ),
+            (9, 26, 52)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_parameters_are_validated() {
+        let json = r#"{"short_period":0,"medium_period":0,"long_period":0}"#;
+        assert!(serde_json::from_str::<IchimokuCloudParameters>(json).is_err());
+        let json = r#"{"short_period":9,"medium_period":26,"long_period":52}"#;
+        assert_eq!(
+            serde_json::from_str::<IchimokuCloudParameters>(json).unwrap(),
+            IchimokuCloudParameters::default()
+        );
+    }
+
+    #[test]
+    fn lines_use_rolling_windows() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut cloud = IchimokuCloud::new(parameters);
+        let candles: Vec<_> = [
+            (10.0, 8.0),
+            (12.0, 9.0),
+            (11.0, 7.0),
+            (13.0, 10.0),
+            (14.0, 12.0),
+            (15.0, 13.0),
+            (16.0, 14.0),
+        ]
+        .iter()
+        .map(|&(high, low)| Candlestick::ohlc(low, high, low, high))
+        .collect();
+        let lines: Vec<_> = candles
+            .iter()
+            .map(|candle| {
+                cloud.calculate(candle).map(|result| {
+                    (
+                        result.tenkan_sen,
+                        result.kijun_sen,
+                        result.senkou_span_a,
+                        result.senkou_span_b,
+                    )
+                })
+            })
+            .collect();
+
+        assert_eq!(lines[..3], [None, None, None]);
+        assert_eq!(lines[3], Some((10.0, 10.0, 10.0, 10.0)));
+        assert_eq!(lines[4], Some((12.0, 10.5, 11.25, 10.5)));
+        // The low of 7 leaves the Tenkan-sen and then the Kijun-sen window
+        assert_eq!(lines[5], Some((13.5, 12.5, 13.0, 11.0)));
+        assert_eq!(lines[6], Some((14.5, 14.0, 14.25, 13.0)));
+    }
+
+    #[test]
+    fn leading_spans_are_shifted_forward() {
+        let parameters = IchimokuCloudParameters::new(3, 6, 10).unwrap();
+        let candles: Vec<_> = (0..60)
+            .map(|i| {
+                let close = 100.0 + (i % 7) as f64;
+                Candlestick::new(
+                    close,
+                    close + 2.0,
+                    close - 3.0,
+                    close,
+                    crate::candlestick::TimeFrame::OneHour,
+                    Some(i * 3_600),
+                    1,
+                    None,
+                    CandlestickState::Closed,
+                )
+                .unwrap()
+            })
+            .collect();
+        let mut cloud = IchimokuCloud::new(parameters);
+        let results: Vec<_> = candles.iter().map(|c| cloud.calculate(c)).collect();
+
+        let first = results.iter().position(Option::is_some).unwrap();
+        assert_eq!(first, 9);
+        let first_cloud = results
+            .iter()
+            .position(|r| r.as_ref().is_some_and(|r| r.current_span_a.is_some()))
+            .unwrap();
+        assert_eq!(first_cloud, first + 6);
+
+        for i in first..60 {
+            let result = results[i].as_ref().unwrap();
+            assert_eq!(result.projected_timestamp, Some((i as i64 + 6) * 3_600));
+            if i >= first_cloud {
+                let earlier = results[i - 6].as_ref().unwrap();
+                assert_eq!(result.current_span_a, Some(earlier.senkou_span_a));
+                assert_eq!(result.current_span_b, Some(earlier.senkou_span_b));
+            }
+        }
+
+        let projected = cloud.projected_cloud();
+        assert_eq!(projected.len(), 6);
+        assert_eq!(projected[0].0, 60 * 3_600);
+        assert_eq!(projected[5].0, 65 * 3_600);
+        let last = results[59].as_ref().unwrap();
+        assert_eq!(
+            projected[5],
+            (65 * 3_600, last.senkou_span_a, last.senkou_span_b)
+        );
+    }
+
+    #[test]
+    fn non_finite_candles_are_skipped() {
+        let parameters = IchimokuCloudParameters::new(1, 2, 3).unwrap();
+        let candle = |close: f64| Candlestick {
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            time_frame: crate::candlestick::TimeFrame::OneHour,
+            timestamp: None,
+            number_of_trades: 1,
+            volume: None,
+            state: CandlestickState::Closed,
+            synthetic: false,
+        };
+        let mut clean = IchimokuCloud::new(parameters);
+        let mut poisoned = IchimokuCloud::new(parameters);
+        for i in 0..10 {
+            let close = 100.0 + i as f64;
+            if i == 5 {
+                assert_eq!(poisoned.peek(&candle(f64::NAN)), None);
+                assert_eq!(poisoned.calculate(&candle(f64::NAN)), None);
+                assert_eq!(poisoned.calculate(&candle(f64::INFINITY)), None);
+            }
+            assert_eq!(
+                poisoned.calculate(&candle(close)),
+                clean.calculate(&candle(close))
+            );
+        }
+    }
+
+    fn trending(step: f64) -> Vec<Candlestick> {
+        (0..20)
+            .map(|i| {
+                let price = 100.0 + step * i as f64;
+                Candlestick::ohlc(price, price + 1.0, price - 1.0, price)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn signal_follows_the_trend() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut cloud = IchimokuCloud::new(parameters);
+        let rising = trending(1.0);
+        assert_eq!(cloud.signal(&rising[0]), IchimokuSignal::Neutral);
+        cloud.warm_up(&rising);
+        assert_eq!(cloud.signal(&rising[19]), IchimokuSignal::StrongBull);
+
+        cloud.reset();
+        let falling = trending(-1.0);
+        cloud.warm_up(&falling);
+        assert_eq!(cloud.signal(&falling[19]), IchimokuSignal::StrongBear);
+
+        // A flat market sits right on a flat cloud
+        cloud.reset();
+        let flat = trending(0.0);
+        cloud.warm_up(&flat);
+        assert_eq!(cloud.signal(&flat[19]), IchimokuSignal::Neutral);
+    }
+
+    #[test]
+    fn chikou_is_compared_with_the_close_26_bars_back() {
+        let mut cloud = IchimokuCloud::new(IchimokuCloudParameters::default());
+        let closes: Vec<f64> = (0..60).map(|i| 100.0 + (i % 7) as f64).collect();
+        for (i, &close) in closes.iter().enumerate() {
+            cloud.calculate(&Candlestick::ohlc(close, close, close, close));
+            let expected = i.checked_sub(26).map(|past| close.total_cmp(&closes[past]));
+            assert_eq!(cloud.chikou_vs_price(), expected);
+        }
+    }
+
+    #[test]
+    fn a_turn_crosses_once() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut cloud = IchimokuCloud::new(parameters);
+        let prices = (0..10)
+            .map(|i| 110.0 - i as f64)
+            .chain((0..10).map(|i| 101.0 + i as f64));
+        let crosses: Vec<_> = prices
+            .enumerate()
+            .filter_map(|(i, price)| {
+                let candle = Candlestick::ohlc(price, price + 1.0, price - 1.0, price);
+                let (_, cross) = cloud.calculate_with_cross(&candle)?;
+                (cross != Cross::None).then_some((i, cross))
+            })
+            .collect();
+        // Both lines are at 101.5 on bar 11, the Tenkan-sen pulls ahead on bar 12
+        assert_eq!(crosses, [(12, Cross::BullishTk)]);
+    }
+
+    #[test]
+    fn reset_starts_over() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut cloud = IchimokuCloud::new(parameters);
+        cloud.warm_up(&trending(1.0));
+        cloud.reset();
+        let mut fresh = IchimokuCloud::new(parameters);
+        for candle in trending(-1.0) {
+            assert_eq!(cloud.calculate(&candle), fresh.calculate(&candle));
+            assert_eq!(cloud.chikou_vs_price(), fresh.chikou_vs_price());
+        }
+        assert_eq!(cloud.projected_cloud(), fresh.projected_cloud());
+        assert_eq!(cloud.num_processed(), fresh.num_processed());
+    }
+
+    #[test]
+    fn displays_the_five_lines() {
+        use alloc::string::ToString;
+
+        let result = IchimokuCloudResult {
+            tenkan_sen: 101.5,
+            kijun_sen: 100.25,
+            senkou_span_a: 100.875,
+            senkou_span_b: 99.0,
+            projected_timestamp: Some(3_600),
+            current_span_a: None,
+            current_span_b: None,
+            chikou_span: 1.0 / 3.0,
+        };
+        assert_eq!(
+            result.to_string(),
+            "tenkan_sen: 101.50000000, kijun_sen: 100.25000000, senkou_span_a: 100.87500000, \
+             senkou_span_b: 99.00000000, chikou_span: 0.33333333"
+        );
+    }
+
+    #[test]
+    fn warm_up_counts_every_candle() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut cloud = IchimokuCloud::new(parameters);
+        let candles = trending(1.0);
+        let last = cloud.warm_up(&candles);
+        assert_eq!(cloud.num_processed(), candles.len());
+        assert_eq!(
+            last,
+            IchimokuCloud::new(parameters).initialize(&candles)[19].1
+        );
+    }
+
+    #[test]
+    fn streaming_after_warm_up_counts_once() {
+        let mut cloud = IchimokuCloud::new(IchimokuCloudParameters::default());
+        let candles: Vec<_> = (0..53)
+            .map(|i| {
+                let price = 100.0 + (i % 5) as f64;
+                Candlestick::ohlc(price, price + 1.0, price - 1.0, price)
+            })
+            .collect();
+        assert!(cloud.warm_up(&candles[..52]).is_some());
+        assert_eq!(cloud.num_processed(), 52);
+        // An open candle is computed but not counted, its closed version is
+        let open = Candlestick {
+            state: CandlestickState::Open,
+            ..candles[52].clone()
+        };
+        assert!(cloud.calculate(&open).is_some());
+        assert_eq!(cloud.num_processed(), 52);
+        assert!(cloud.calculate(&candles[52]).is_some());
+        assert_eq!(cloud.num_processed(), 53);
+    }
+
+    #[test]
+    fn results_are_rounded_to_the_precision() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let candles: Vec<_> = (0..10)
+            .map(|i| {
+                let price = 100.0 + i as f64 / 3.0;
+                Candlestick::ohlc(price, price + 0.001, price - 0.001, price)
+            })
+            .collect();
+        let mut cloud = IchimokuCloud::new(parameters).with_precision(2);
+        let result = cloud.warm_up(&candles).unwrap();
+        let precise = IchimokuCloud::new(parameters).warm_up(&candles).unwrap();
+        assert_eq!(precise.tenkan_sen, 102.83333333);
+        assert_eq!(
+            (
+                result.tenkan_sen,
+                result.kijun_sen,
+                result.senkou_span_a,
+                result.senkou_span_b,
+                result.chikou_span
+            ),
+            (102.83, 102.67, 102.75, 102.5, 103.0)
+        );
+        for (_, span_a, span_b) in cloud.projected_cloud() {
+            assert_eq!((round_to(span_a, 2), round_to(span_b, 2)), (span_a, span_b));
+        }
+    }
+
+    #[test]
+    fn ema_mode_smooths_the_midprice() {
+        let parameters = IchimokuCloudParameters::new(2, 3, 4).unwrap();
+        let mut candles = vec![Candlestick::ohlc(100.0, 101.0, 99.0, 100.0); 6];
+        candles.push(Candlestick::ohlc(110.0, 111.0, 109.0, 110.0));
+
+        let classic = IchimokuCloud::new(parameters).warm_up(&candles).unwrap();
+        let ema = IchimokuCloud::new(parameters)
+            .with_mode(IchimokuMode::Ema)
+            .warm_up(&candles)
+            .unwrap();
+        // The windows span the jump, the EMAs move 2/3 and 1/2 of the way to 110
+        assert_eq!((classic.tenkan_sen, classic.kijun_sen), (105.0, 105.0));
+        assert_eq!((ema.tenkan_sen, ema.kijun_sen), (106.66666667, 105.0));
+        // Senkou span B is the long window midpoint in both modes
+        assert_eq!(ema.senkou_span_b, classic.senkou_span_b);
+    }
+
+    #[test]
+    fn classic_mode_is_the_window_midpoint() {
+        let parameters = IchimokuCloudParameters::new(3, 5, 8).unwrap();
+        let candles: Vec<_> = (0..40)
+            .map(|i| {
+                let price = 100.0 + ((i * 7) % 11) as f64;
+                Candlestick::ohlc(price, price + (i % 3) as f64, price - 1.5, price)
+            })
+            .collect();
+        let midpoint = |window: &[Candlestick]| {
+            let high = window.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+            let low = window.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+            round_to((high + low) / 2.0, 8)
+        };
+
+        let mut cloud = IchimokuCloud::new(parameters);
+        for (i, candle) in candles.iter().enumerate() {
+            let Some(result) = cloud.calculate(candle) else {
+                continue;
+            };
+            assert_eq!(result.tenkan_sen, midpoint(&candles[i - 2..=i]));
+            assert_eq!(result.kijun_sen, midpoint(&candles[i - 4..=i]));
+            assert_eq!(result.senkou_span_b, midpoint(&candles[i - 7..=i]));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::candlestick::Candlestick;
+
+// Common streaming interface, so indicators can be driven uniformly from one candle
+// stream, e.g. as `Vec<Box<dyn Indicator<Output = f64>>>`.
+pub trait Indicator {
+    type Output;
+
+    // Feed the next candle, returning None while the indicator is warming up.
+    fn update(&mut self, candle: &Candlestick) -> Option<Self::Output>;
+
+    // Forget everything seen so far, keeping the configuration.
+    fn reset(&mut self);
+
+    // Candles `update` still needs before it returns its first value, counting the one
+    // that does, so 0 from then on, e.g. for showing warm-up progress. Candles an
+    // indicator can't use, like ones without volume for a volume based indicator, don't
+    // count, and such a candle can still get None after the warm-up.
+    fn bars_until_ready(&self) -> usize;
+
+    fn is_ready(&self) -> bool {
+        self.bars_until_ready() == 0
+    }
+}
+
+// Warm-up of an indicator fed by another one. The second gets its first value on the
+// candle the first one gets ready, so that candle counts for both.
+pub(crate) fn chained(first: usize, second: usize) -> usize {
+    match first {
+        0 => second,
+        _ => first + second.saturating_sub(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aroon::Aroon;
+    use crate::atr::AverageTrueRange;
+    use crate::bollinger::BollingerBands;
+    use crate::cci::CommodityChannelIndex;
+    use crate::cmf::ChaikinMoneyFlow;
+    use crate::cmo::ChandeMomentum;
+    use crate::coppock::Coppock;
+    use crate::dmi::DirectionalMovement;
+    use crate::donchian::DonchianChannels;
+    use crate::dpo::DetrendedPriceOscillator;
+    use crate::ema::ExponentialMovingAverage;
+    use crate::finite::{FiniteGuard, NonFinitePolicy};
+    use crate::hull::HullMovingAverage;
+    use crate::ichimoku::{IchimokuCloud, IchimokuCloudParameters, IchimokuMode};
+    use crate::keltner::KeltnerChannels;
+    use crate::macd::Macd;
+    use crate::mfi::MoneyFlowIndex;
+    use crate::momentum::{Momentum, RateOfChange};
+    use crate::obv::OnBalanceVolume;
+    use crate::parabolic_sar::ParabolicSar;
+    use crate::rolling_quantile::RollingQuantile;
+    use crate::rsi::RelativeStrengthIndex;
+    use crate::sma::SimpleMovingAverage;
+    use crate::squeeze::SqueezeDetector;
+    use crate::standard_error::StandardErrorBands;
+    use crate::stochastic::StochasticOscillator;
+    use crate::trix::Trix;
+    use crate::ultimate::UltimateOscillator;
+    use crate::vwap::Vwap;
+    use crate::williams_r::WilliamsR;
+    use crate::wma::WeightedMovingAverage;
+    use crate::zscore::RollingZScore;
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // A choppy uptrend with varying bodies, ranges and volumes.
+    fn series() -> Vec<Candlestick> {
+        (0..150)
+            .map(|i| {
+                let close = 100.0 + ((i * 37) % 23) as f64 - 11.0 + i as f64 / 10.0;
+                let open = close - ((i * 11) % 5) as f64 + 2.0;
+                Candlestick {
+                    volume: Some(1000.0 + ((i * 13) % 7) as f64 * 50.0),
+                    ..Candlestick::ohlc(open, open.max(close) + 1.0, open.min(close) - 1.0, close)
+                }
+            })
+            .collect()
+    }
+
+    // The first value comes exactly when the countdown says, right away for an indicator
+    // starting at 0, and the indicator stays ready from then on.
+    fn assert_counts_down<I: Indicator>(name: &str, mut indicator: I) {
+        let mut ready = false;
+        for (index, candle) in series().iter().enumerate() {
+            let before = indicator.bars_until_ready();
+            let has_value = indicator.update(candle).is_some();
+            if !ready {
+                assert_eq!(has_value, before <= 1, "{} at {}", name, index);
+                assert_eq!(indicator.is_ready(), has_value, "{} at {}", name, index);
+            }
+            ready |= has_value;
+            assert_eq!(indicator.is_ready(), ready, "{} at {}", name, index);
+        }
+        assert!(ready, "{} never got ready", name);
+    }
+
+    #[test]
+    fn indicators_run_side_by_side_as_trait_objects() {
+        let mut indicators: Vec<Box<dyn Indicator<Output = f64>>> = vec![
+            Box::new(SimpleMovingAverage::new(2)),
+            Box::new(AverageTrueRange::new(2)),
+        ];
+        let candles = [
+            Candlestick::ohlc(1.0, 2.0, 0.5, 1.5),
+            Candlestick::ohlc(1.5, 2.5, 1.0, 2.0),
+        ];
+        let mut outputs = Vec::new();
+        for candle in &candles {
+            for indicator in indicators.iter_mut() {
+                outputs.push(indicator.update(candle));
+            }
+        }
+        assert_eq!(outputs, [None, None, Some(1.75), Some(1.5)]);
+
+        for indicator in indicators.iter_mut() {
+            indicator.reset();
+            assert_eq!(indicator.bars_until_ready(), 2);
+        }
+    }
+
+    #[test]
+    fn bars_until_ready_counts_down() {
+        for p in [1, 2, 3, 5, 9] {
+            assert_counts_down("aroon", Aroon::new(p));
+            assert_counts_down("atr", AverageTrueRange::new(p));
+            assert_counts_down("bollinger", BollingerBands::new(p, 2.0));
+            assert_counts_down("cci", CommodityChannelIndex::new(p));
+            assert_counts_down("cmf", ChaikinMoneyFlow::new(p));
+            assert_counts_down("cmo", ChandeMomentum::new(p));
+            assert_counts_down("coppock", Coppock::new(p + 3, p, p));
+            assert_counts_down("dmi", DirectionalMovement::new(p));
+            assert_counts_down("donchian", DonchianChannels::new(p));
+            assert_counts_down("dpo", DetrendedPriceOscillator::new(p));
+            assert_counts_down("ema", ExponentialMovingAverage::new_seeded(p));
+            assert_counts_down("hull", HullMovingAverage::new(p));
+            assert_counts_down("keltner", KeltnerChannels::new(p, p + 2, 1.5));
+            assert_counts_down("keltner", KeltnerChannels::new(p + 2, p, 1.5));
+            assert_counts_down("macd", Macd::new(p, p + 2, p + 1));
+            assert_counts_down("mfi", MoneyFlowIndex::new(p));
+            assert_counts_down("momentum", Momentum::new(p));
+            assert_counts_down("roc", RateOfChange::new(p));
+            assert_counts_down("quantile", RollingQuantile::new(p, 0.3));
+            assert_counts_down("rsi", RelativeStrengthIndex::new(p));
+            assert_counts_down("sma", SimpleMovingAverage::new(p));
+            assert_counts_down("squeeze", SqueezeDetector::new(p, 2.0, 1.5));
+            assert_counts_down("standard error", StandardErrorBands::new(p, 2.0));
+            assert_counts_down("stochastic", StochasticOscillator::new(p, p + 1));
+            assert_counts_down("trix", Trix::new(p));
+            assert_counts_down("ultimate", UltimateOscillator::new(p, p + 1, p + 3));
+            assert_counts_down("williams %r", WilliamsR::new(p));
+            assert_counts_down("wma", WeightedMovingAverage::new(p));
+            assert_counts_down("z-score", RollingZScore::new(p + 1));
+            assert_counts_down(
+                "finite",
+                FiniteGuard::new(SimpleMovingAverage::new(p), NonFinitePolicy::Skip),
+            );
+            let parameters = IchimokuCloudParameters::new(p, p + 1, p + 4).unwrap();
+            assert_counts_down("ichimoku", IchimokuCloud::new(parameters));
+            assert_counts_down(
+                "ichimoku ema",
+                IchimokuCloud::new(parameters).with_mode(IchimokuMode::Ema),
+            );
+        }
+        assert_counts_down("obv", OnBalanceVolume::new());
+        assert_counts_down("parabolic sar", ParabolicSar::default());
+        assert_counts_down("vwap", Vwap::new());
+    }
+}
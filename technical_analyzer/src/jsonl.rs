@@ -0,0 +1,235 @@
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::candlestick::{CandleError, Candlestick, CandlestickState, TimeFrame};
+use crate::units::Volume;
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    // The line isn't a JSON object
+    Syntax { line: usize, message: String },
+    MissingField { line: usize, field: &'static str },
+    InvalidField { line: usize, field: &'static str },
+    InvalidCandle { line: usize, error: CandleError },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Io(error) => write!(f, "{}", error),
+            ParseError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ParseError::MissingField { line, field } => {
+                write!(f, "line {}: missing {}", line, field)
+            }
+            ParseError::InvalidField { line, field } => {
+                write!(f, "line {}: invalid {}", line, field)
+            }
+            ParseError::InvalidCandle { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<io::Error> for ParseError {
+    fn from(error: io::Error) -> Self {
+        ParseError::Io(error)
+    }
+}
+
+// Read candles from JSON lines, one object per line such as
+// {"timestamp": 60, "open": 1.0, "high": 2.0, "low": 0.5, "close": 1.5, "trades": 3}.
+// open, high, low and close are required, timestamp, trades and volume optional. Numbers
+// may also be given as strings, as some exchanges do, and other keys are ignored. Blank
+// lines are skipped. Candles are Closed unless they have "closed": false, which makes
+// them Open. Lines are parsed as they are read, so this works on a stream like stdin.
+pub fn read_candles_jsonl<R: BufRead>(
+    reader: R,
+    time_frame: TimeFrame,
+) -> impl Iterator<Item = Result<Candlestick, ParseError>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(move |(index, line)| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(parse_candle(index + 1, &line, time_frame)),
+            Err(error) => Some(Err(ParseError::Io(error))),
+        })
+}
+
+// One candle object, see `read_candles_jsonl`. `line` only numbers the errors.
+pub(crate) fn parse_candle(
+    line: usize,
+    text: &str,
+    time_frame: TimeFrame,
+) -> Result<Candlestick, ParseError> {
+    let fields = match serde_json::from_str(text) {
+        Ok(Value::Object(fields)) => fields,
+        Ok(_) => {
+            return Err(ParseError::Syntax {
+                line,
+                message: "expected an object".to_string(),
+            })
+        }
+        Err(error) => {
+            return Err(ParseError::Syntax {
+                line,
+                message: error.to_string(),
+            })
+        }
+    };
+
+    // A null counts as missing
+    let field = |name: &'static str| fields.get(name).filter(|value| !value.is_null());
+    let required = |name: &'static str| -> Result<f64, ParseError> {
+        let value = field(name).ok_or(ParseError::MissingField { line, field: name })?;
+        parse_number(value, line, name)
+    };
+    fn optional<T: FromStr>(
+        value: Option<&Value>,
+        line: usize,
+        name: &'static str,
+    ) -> Result<Option<T>, ParseError> {
+        value
+            .map(|value| parse_number(value, line, name))
+            .transpose()
+    }
+    let state = match field("closed") {
+        None | Some(Value::Bool(true)) => CandlestickState::Closed,
+        Some(Value::Bool(false)) => CandlestickState::Open,
+        Some(_) => {
+            return Err(ParseError::InvalidField {
+                line,
+                field: "closed",
+            })
+        }
+    };
+
+    Candlestick::new(
+        required("open")?,
+        required("high")?,
+        required("low")?,
+        required("close")?,
+        time_frame,
+        optional(field("timestamp"), line, "timestamp")?,
+        optional(field("trades"), line, "trades")?.unwrap_or_default(),
+        optional::<f64>(field("volume"), line, "volume")?.map(Volume::from),
+        state,
+    )
+    .map_err(|error| ParseError::InvalidCandle { line, error })
+}
+
+// A number, or a string holding one. Both go through their text so integers parse
+// exactly.
+fn parse_number<T: FromStr>(
+    value: &Value,
+    line: usize,
+    field: &'static str,
+) -> Result<T, ParseError> {
+    match value {
+        Value::Number(number) => number.to_string().parse().ok(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or(ParseError::InvalidField { line, field })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn read(text: &str) -> Vec<Result<Candlestick, ParseError>> {
+        read_candles_jsonl(Cursor::new(text.to_string()), TimeFrame::OneMinute).collect()
+    }
+
+    #[test]
+    fn reads_candles_line_by_line() {
+        let candles: Vec<_> = read(concat!(
+            "{\"timestamp\": 60, \"open\": 1, \"high\": 2, \"low\": 0.5, \"close\": 1.5, \"trades\": 3}\n",
+            "\n",
+            "{\"open\": \"1.5\", \"high\": \"2.5\", \"low\": \"1\", \"close\": \"2\", \"volume\": 12.5, ",
+            "\"timestamp\": null, \"symbol\": \"BTCUSDT\", \"extra\": [1, 2], \"closed\": false}\n",
+        ))
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].timestamp, Some(60));
+        assert_eq!(candles[0].number_of_trades, 3);
+        assert_eq!(candles[0].volume, None);
+        assert_eq!(candles[0].state, CandlestickState::Closed);
+        assert_eq!(candles[1].close, 2.0);
+        assert_eq!(candles[1].timestamp, None);
+        assert_eq!(candles[1].volume, Some(12.5));
+        assert_eq!(candles[1].state, CandlestickState::Open);
+    }
+
+    #[test]
+    fn round_trips_candles_written_as_json() {
+        let written: Vec<_> = (0..3)
+            .map(|i| Candlestick {
+                timestamp: Some(60 * i),
+                number_of_trades: i as u32 + 1,
+                volume: (i > 0).then_some(2.5 * i as f64),
+                ..Candlestick::ohlc(1.25, 2.5, 0.75, 2.0)
+            })
+            .collect();
+        let text: String = written
+            .iter()
+            .map(|candle| {
+                let mut line = serde_json::json!({
+                    "timestamp": candle.timestamp,
+                    "open": candle.open,
+                    "high": candle.high,
+                    "low": candle.low,
+                    "close": candle.close,
+                    "trades": candle.number_of_trades,
+                });
+                if let Some(volume) = candle.volume {
+                    line["volume"] = volume.into();
+                }
+                line.to_string() + "\n"
+            })
+            .collect();
+
+        let read: Vec<_> = read(&text).into_iter().map(Result::unwrap).collect();
+        assert_eq!(read.len(), written.len());
+        for (read, written) in read.iter().zip(&written) {
+            assert_eq!(read.timestamp, written.timestamp);
+            assert_eq!(
+                (read.open, read.high, read.low, read.close),
+                (written.open, written.high, written.low, written.close)
+            );
+            assert_eq!(read.number_of_trades, written.number_of_trades);
+            assert_eq!(read.volume, written.volume);
+        }
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let errors: Vec<_> = read(concat!(
+            "{\"open\": 1, \"high\": 2, \"low\": 1}\n",
+            "{\"open\": 1, \"high\": x}\n",
+            "{\"open\": 1, \"high\": \"x\", \"low\": 1, \"close\": 1}\n",
+            "{\"open\": 1, \"high\": 2, \"low\": 1, \"close\": 1, \"closed\": 0}\n",
+            "{\"open\": 1, \"high\": 1, \"low\": 2, \"close\": 1}\n",
+            "[1, 2]\n",
+        ))
+        .into_iter()
+        .map(|result| result.unwrap_err().to_string())
+        .collect();
+
+        assert_eq!(errors[0], "line 1: missing close");
+        assert!(errors[1].starts_with("line 2: expected value"));
+        assert_eq!(errors[2], "line 3: invalid high");
+        assert_eq!(errors[3], "line 4: invalid closed");
+        assert!(errors[4].starts_with("line 5: "));
+        assert_eq!(errors[5], "line 6: expected an object");
+    }
+}
@@ -0,0 +1,109 @@
+use crate::atr::AverageTrueRange;
+use crate::candlestick::Candlestick;
+use crate::ema::ExponentialMovingAverage;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeltnerResult {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+pub struct KeltnerChannels {
+    ema_period: usize,
+    multiplier: f64,
+    ema: ExponentialMovingAverage,
+    atr: AverageTrueRange,
+    num_processed: usize,
+}
+
+impl KeltnerChannels {
+    pub fn new(ema_period: usize, atr_period: usize, multiplier: f64) -> Self {
+        Self {
+            ema_period,
+            multiplier,
+            ema: ExponentialMovingAverage::new_seeded(ema_period),
+            atr: AverageTrueRange::new(atr_period),
+            num_processed: 0,
+        }
+    }
+
+    // The middle line is the EMA of the close, the channels are `multiplier` ATRs away
+    // from it. Returns None until both averages have seen a full period.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<KeltnerResult> {
+        // A non-finite close is skipped before it's counted
+        let middle = self.ema.update(candle.close)?;
+        self.num_processed += 1;
+        let atr = self.atr.update(candle)?;
+        if self.num_processed < self.ema_period {
+            return None;
+        }
+
+        let offset = self.multiplier * atr;
+        Some(KeltnerResult {
+            upper: round_to(middle + offset, 8),
+            middle,
+            lower: round_to(middle - offset, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.atr.reset();
+        self.num_processed = 0;
+    }
+}
+
+impl Indicator for KeltnerChannels {
+    type Output = KeltnerResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<KeltnerResult> {
+        KeltnerChannels::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        KeltnerChannels::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.ema_period
+            .saturating_sub(self.num_processed)
+            .max(self.atr.bars_until_ready())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channels_are_atrs_away_from_the_ema() {
+        let mut keltner = KeltnerChannels::new(3, 2, 2.0);
+        let candle = Candlestick::ohlc(10.0, 11.0, 9.0, 10.0);
+        assert_eq!(keltner.bars_until_ready(), 3);
+        assert!(keltner.update(&candle).is_none());
+        assert!(keltner.update(&candle).is_none());
+        assert_eq!(keltner.bars_until_ready(), 1);
+
+        // A constant range of 2 around a flat close
+        let result = keltner.update(&candle).unwrap();
+        assert_eq!(
+            (result.upper, result.middle, result.lower),
+            (14.0, 10.0, 6.0)
+        );
+
+        // The close jumps by 4, the EMA moves half way and the true range is 5
+        let result = keltner
+            .update(&Candlestick::ohlc(13.0, 15.0, 13.0, 14.0))
+            .unwrap();
+        assert_eq!(
+            (result.upper, result.middle, result.lower),
+            (19.0, 12.0, 5.0)
+        );
+
+        keltner.reset();
+        assert_eq!(keltner.bars_until_ready(), 3);
+    }
+}
@@ -0,0 +1,153 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod aggregate;
+mod aroon;
+mod atr;
+mod backtest;
+#[cfg(feature = "std")]
+mod batch;
+mod bollinger;
+mod candlestick;
+mod cci;
+mod clock;
+mod cmf;
+mod cmo;
+mod consensus;
+mod coppock;
+mod correlation;
+#[cfg(feature = "std")]
+mod csv;
+mod dmi;
+mod donchian;
+mod dpo;
+mod ema;
+#[cfg(feature = "websocket")]
+mod feed;
+mod fibonacci;
+mod finite;
+mod gaps;
+mod heikin_ashi;
+mod high_low;
+mod hull;
+mod ichimoku;
+mod indicator;
+#[cfg(all(feature = "std", feature = "serde"))]
+mod jsonl;
+mod keltner;
+mod macd;
+mod mfi;
+mod momentum;
+#[cfg(feature = "std")]
+mod multi_timeframe;
+mod normalize;
+mod obv;
+mod pairs;
+mod parabolic_sar;
+pub mod patterns;
+#[cfg(feature = "std")]
+mod pipeline;
+mod pivots;
+mod records;
+mod regression;
+mod renko;
+mod rolling_extrema;
+mod rolling_quantile;
+mod rolling_std_dev;
+mod rsi;
+mod session;
+mod sma;
+mod squeeze;
+mod standard_error;
+mod stochastic;
+mod trailing_stop;
+mod trix;
+mod ultimate;
+mod units;
+mod utils;
+mod vwap;
+mod williams_r;
+mod window;
+mod wma;
+mod zscore;
+
+pub use aggregate::{aggregate, AggregationError, CandleAggregator};
+pub use aroon::{Aroon, AroonResult};
+pub use atr::AverageTrueRange;
+pub use backtest::{
+    BacktestError, BacktestReport, Backtester, IndicatorState, Order, PositionSize, Trade,
+};
+#[cfg(feature = "rayon")]
+pub use batch::compute_parallel;
+#[cfg(feature = "std")]
+pub use batch::compute_serial;
+pub use bollinger::{BollingerBands, BollingerResult};
+pub use candlestick::{
+    CandleError, Candlestick, CandlestickBuilder, CandlestickState, ParseTimeFrameError, TimeFrame,
+};
+pub use cci::CommodityChannelIndex;
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, ReplayClock};
+pub use cmf::ChaikinMoneyFlow;
+pub use cmo::ChandeMomentum;
+pub use consensus::{
+    consensus, consensus_with, Consensus, IndicatorSignal, SignalSource, SignalWeights, Vote,
+};
+pub use coppock::Coppock;
+pub use correlation::RollingCorrelation;
+#[cfg(feature = "std")]
+pub use csv::{read_candles_csv, write_candles_csv, write_ichimoku_csv};
+pub use dmi::{DirectionalMovement, DmiResult, TrendStrength};
+pub use donchian::{DonchianChannels, DonchianResult};
+pub use dpo::DetrendedPriceOscillator;
+pub use ema::ExponentialMovingAverage;
+#[cfg(feature = "websocket")]
+pub use feed::{connect_feed, CandleFormat, FeedError, JsonCandleFormat};
+pub use fibonacci::fib_retracements;
+pub use finite::{FiniteGuard, NonFiniteInput, NonFinitePolicy};
+pub use gaps::{fill_gaps, find_gaps, gap, GapKind};
+pub use heikin_ashi::{heikin_ashi, ichimoku_heikin_ashi};
+pub use hull::HullMovingAverage;
+pub use ichimoku::{
+    Cross, IchimokuCloud, IchimokuCloudParameters, IchimokuCloudResult, IchimokuMode,
+    IchimokuSignal, ParamError,
+};
+pub use indicator::Indicator;
+#[cfg(all(feature = "std", feature = "serde"))]
+pub use jsonl::{read_candles_jsonl, ParseError};
+pub use keltner::{KeltnerChannels, KeltnerResult};
+pub use macd::{Macd, MacdResult};
+pub use mfi::MoneyFlowIndex;
+pub use momentum::{Momentum, RateOfChange};
+#[cfg(feature = "std")]
+pub use multi_timeframe::MultiTimeframeIchimoku;
+pub use normalize::normalize;
+pub use obv::OnBalanceVolume;
+pub use pairs::{spread, PairError};
+pub use parabolic_sar::ParabolicSar;
+#[cfg(feature = "std")]
+pub use pipeline::{IndicatorValue, Pipeline};
+pub use pivots::{pivot_points, PivotLevels, PivotMethod};
+pub use records::{to_records, IndicatorRecord};
+pub use regression::{linear_regression, regression_channel, RegressionChannel};
+pub use renko::{renko, Direction, RenkoBrick, MAX_BRICKS};
+pub use rolling_extrema::RollingExtrema;
+pub use rolling_quantile::RollingQuantile;
+pub use rolling_std_dev::RollingStdDev;
+pub use rsi::RelativeStrengthIndex;
+pub use session::session_boundaries;
+pub use sma::SimpleMovingAverage;
+pub use squeeze::{SqueezeDetector, SqueezeState};
+pub use standard_error::{StandardErrorBands, StandardErrorResult};
+pub use stochastic::{StochResult, StochasticOscillator};
+pub use trailing_stop::trailing_stop;
+pub use trix::Trix;
+pub use ultimate::UltimateOscillator;
+pub use units::{Price, Volume};
+pub use vwap::Vwap;
+pub use williams_r::WilliamsR;
+pub use window::Window;
+pub use wma::WeightedMovingAverage;
+pub use zscore::RollingZScore;
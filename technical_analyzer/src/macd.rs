@@ -0,0 +1,128 @@
+use crate::candlestick::Candlestick;
+use crate::ema::ExponentialMovingAverage;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacdResult {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+pub struct Macd {
+    fast: ExponentialMovingAverage,
+    slow: ExponentialMovingAverage,
+    signal: ExponentialMovingAverage,
+    slow_period: usize,
+    num_processed: usize,
+}
+
+impl Macd {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        Self {
+            fast: ExponentialMovingAverage::new_seeded(fast_period),
+            slow: ExponentialMovingAverage::new_seeded(slow_period),
+            signal: ExponentialMovingAverage::new_seeded(signal_period),
+            slow_period,
+            num_processed: 0,
+        }
+    }
+
+    // Returns None until the slow average has seen `slow_period` closes. The signal line
+    // only starts from the first MACD value.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<MacdResult> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        self.num_processed += 1;
+        let fast = self.fast.update(close)?;
+        let slow = self.slow.update(close)?;
+        if self.num_processed < self.slow_period {
+            return None;
+        }
+
+        let macd = round_to(fast - slow, 8);
+        let signal = self.signal.update(macd)?;
+        Some(MacdResult {
+            macd,
+            signal,
+            histogram: round_to(macd - signal, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+        self.num_processed = 0;
+    }
+}
+
+impl Default for Macd {
+    // The usual 12/26/9 configuration.
+    fn default() -> Self {
+        Self::new(12, 26, 9)
+    }
+}
+
+impl Indicator for Macd {
+    type Output = MacdResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<MacdResult> {
+        Macd::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        Macd::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.slow_period.saturating_sub(self.num_processed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn signal_follows_the_macd_line() {
+        let mut macd = Macd::new(2, 3, 2);
+        assert!(macd.update(1.0).is_none());
+        assert!(macd.update(2.0).is_none());
+        // Fast 2.5 and slow 2 once the slow average is seeded
+        let first = macd.update(3.0).unwrap();
+        assert_eq!((first.macd, first.signal, first.histogram), (0.5, 0.5, 0.0));
+        let steady = macd.update(4.0).unwrap();
+        assert_eq!(
+            (steady.macd, steady.signal, steady.histogram),
+            (0.5, 0.5, 0.0)
+        );
+
+        // Fast 7.83333333 against slow 6.5
+        let jump = macd.update(10.0).unwrap();
+        assert_eq!(jump.macd, 1.33333333);
+        assert_eq!(jump.signal, 1.05555555);
+        assert_eq!(jump.histogram, 0.27777778);
+
+        macd.reset();
+        assert!(macd.update(10.0).is_none());
+    }
+
+    #[test]
+    fn accelerating_prices_give_a_positive_histogram() {
+        let mut macd = Macd::default();
+        let results: Vec<_> = (0..60)
+            .map(|i| macd.update(100.0 + (i * i) as f64 * 0.1))
+            .collect();
+        assert!(results[24].is_none());
+        assert!(results[25].is_some());
+        let last = results[59].as_ref().unwrap();
+        assert!(last.histogram > 0.0);
+        assert!((last.histogram - (last.macd - last.signal)).abs() < 1e-8);
+    }
+}
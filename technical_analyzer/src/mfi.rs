@@ -0,0 +1,124 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// Volume-weighted RSI of the typical price, (high + low + close) / 3.
+pub struct MoneyFlowIndex {
+    previous_typical_price: Option<f64>,
+    // Positive and negative money flow of the last `period` candles.
+    flows: Window<(f64, f64)>,
+}
+
+impl MoneyFlowIndex {
+    // Panics if `period` is 0, which has no money flow.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            previous_typical_price: None,
+            flows: Window::new(period),
+        }
+    }
+
+    // The money flow of a candle counts as positive when its typical price rose and as
+    // negative when it fell. Returns None until `period + 1` candles have been seen, and
+    // for a candle without volume, which is skipped.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        let volume = candle.volume?;
+        let typical_price = (candle.high + candle.low + candle.close) / 3.0;
+        let previous_typical_price = self.previous_typical_price.replace(typical_price)?;
+
+        let money_flow = typical_price * volume;
+        let flow = if typical_price > previous_typical_price {
+            (money_flow, 0.0)
+        } else if typical_price < previous_typical_price {
+            (0.0, money_flow)
+        } else {
+            (0.0, 0.0)
+        };
+        self.flows.push(flow);
+        if !self.flows.is_full() {
+            return None;
+        }
+
+        let (positive, negative) = self
+            .flows
+            .iter()
+            .fold((0.0, 0.0), |(positive, negative), &(up, down)| {
+                (positive + up, negative + down)
+            });
+        let mfi = if negative == 0.0 {
+            // Only inflows means maximum buying pressure, unless nothing moved at all.
+            if positive == 0.0 {
+                50.0
+            } else {
+                100.0
+            }
+        } else {
+            100.0 - 100.0 / (1.0 + positive / negative)
+        };
+        Some(round_to(mfi, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.previous_typical_price = None;
+        self.flows.clear();
+    }
+}
+
+impl Indicator for MoneyFlowIndex {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        MoneyFlowIndex::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        MoneyFlowIndex::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        usize::from(self.previous_typical_price.is_none()) + self.flows.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn candle(price: f64, volume: f64) -> Candlestick {
+        Candlestick {
+            volume: Some(volume),
+            ..Candlestick::ohlc(price, price, price, price)
+        }
+    }
+
+    #[test]
+    fn splits_money_flow_by_direction() {
+        let mut mfi = MoneyFlowIndex::new(2);
+        assert_eq!(mfi.bars_until_ready(), 3);
+        assert_eq!(mfi.update(&candle(10.0, 1.0)), None);
+        assert_eq!(mfi.update(&candle(11.0, 1.0)), None);
+        // Only inflows
+        assert_eq!(mfi.update(&candle(12.0, 1.0)), Some(100.0));
+        // 12 in against 18 out
+        assert_eq!(mfi.update(&candle(9.0, 2.0)), Some(40.0));
+        // Candles without volume are skipped
+        assert_eq!(mfi.update(&Candlestick::ohlc(50.0, 50.0, 50.0, 50.0)), None);
+        assert_eq!(mfi.update(&candle(9.0, 1.0)), Some(0.0));
+    }
+
+    #[test]
+    fn no_movement_is_neutral() {
+        let mut mfi = MoneyFlowIndex::new(2);
+        let results: Vec<_> = (0..4).map(|_| mfi.update(&candle(10.0, 1.0))).collect();
+        assert_eq!(results, [None, None, Some(50.0), Some(50.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        MoneyFlowIndex::new(0);
+    }
+}
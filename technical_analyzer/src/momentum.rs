@@ -0,0 +1,186 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// The last `period + 1` closes, so the oldest one is the close `period` bars ago.
+struct CloseHistory {
+    closes: Window<f64>,
+}
+
+impl CloseHistory {
+    fn new(period: usize) -> Self {
+        Self {
+            closes: Window::new(period + 1),
+        }
+    }
+
+    // Add a close, returning the close `period` bars before it once there is one.
+    fn push(&mut self, close: f64) -> Option<f64> {
+        self.closes.push(close);
+        if !self.closes.is_full() {
+            return None;
+        }
+        self.closes.oldest().copied()
+    }
+
+    fn remaining(&self) -> usize {
+        self.closes.remaining()
+    }
+
+    fn clear(&mut self) {
+        self.closes.clear();
+    }
+}
+
+// Percentage change of the close over `period` bars.
+pub struct RateOfChange {
+    history: CloseHistory,
+}
+
+impl RateOfChange {
+    // Panics if `period` is 0, which has nothing to compare against.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            history: CloseHistory::new(period),
+        }
+    }
+
+    // Returns None until `period + 1` closes have been seen, or when the close `period`
+    // bars ago was zero.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let past = self.history.push(close)?;
+        if past == 0.0 {
+            return None;
+        }
+        Some(round_to(100.0 * (close - past) / past, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl Indicator for RateOfChange {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        RateOfChange::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        RateOfChange::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.history.remaining()
+    }
+}
+
+// Absolute change of the close over `period` bars.
+pub struct Momentum {
+    history: CloseHistory,
+}
+
+impl Momentum {
+    // Panics if `period` is 0, see `RateOfChange::new`.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            history: CloseHistory::new(period),
+        }
+    }
+
+    // Returns None until `period + 1` closes have been seen.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let past = self.history.push(close)?;
+        Some(round_to(close - past, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl Indicator for Momentum {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        Momentum::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        Momentum::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.history.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn compares_with_the_close_period_bars_ago() {
+        let mut momentum = Momentum::new(2);
+        let mut roc = RateOfChange::new(2);
+        assert_eq!(Indicator::bars_until_ready(&momentum), 3);
+        for close in [10.0, 12.0] {
+            assert_eq!(momentum.update(close), None);
+            assert_eq!(roc.update(close), None);
+        }
+        assert_eq!(momentum.update(15.0), Some(5.0));
+        assert_eq!(roc.update(15.0), Some(50.0));
+        assert_eq!(momentum.update(9.0), Some(-3.0));
+        assert_eq!(roc.update(9.0), Some(-25.0));
+        assert_eq!(Indicator::bars_until_ready(&roc), 0);
+    }
+
+    #[test]
+    fn rate_of_change_from_zero_is_undefined() {
+        let mut roc = RateOfChange::new(1);
+        roc.update(0.0);
+        assert_eq!(roc.update(5.0), None);
+        assert_eq!(roc.update(10.0), Some(100.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        Momentum::new(0);
+    }
+
+    #[test]
+    fn ramps_give_a_constant_output() {
+        // A linear ramp of 2 a bar moves 10 over 5 bars
+        let mut momentum = Momentum::new(5);
+        let changes: Vec<_> = (0..20)
+            .filter_map(|i| momentum.update(100.0 + 2.0 * i as f64))
+            .collect();
+        assert_eq!(changes, [10.0; 15]);
+
+        // A geometric ramp of 25% a bar
+        let mut roc = RateOfChange::new(1);
+        let mut close = 64.0;
+        let rates: Vec<_> = (0..10)
+            .filter_map(|_| {
+                close *= 1.25;
+                roc.update(close)
+            })
+            .collect();
+        assert_eq!(rates, [25.0; 9]);
+    }
+}
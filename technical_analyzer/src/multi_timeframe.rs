@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use crate::aggregate::{merge, start_bucket};
+use crate::candlestick::{Candlestick, CandlestickState, TimeFrame};
+use crate::ichimoku::{IchimokuCloud, IchimokuCloudParameters, IchimokuCloudResult};
+
+// One Ichimoku cloud of a higher time frame and the candle of it still being built. Like
+// `IchimokuCloud::peek`, an Open lower time frame candle is only provisional: it is kept
+// apart from the committed candles until its Closed version replaces it, so its trades and
+// volume aren't counted twice.
+struct TimeFrameCloud {
+    cloud: IchimokuCloud,
+    // Start of the bucket being built
+    bucket: Option<i64>,
+    // The Closed lower time frame candles of the bucket merged together
+    committed: Option<Candlestick>,
+    // The latest Open lower time frame candle of the bucket
+    provisional: Option<Candlestick>,
+    last_result: Option<IchimokuCloudResult>,
+}
+
+impl TimeFrameCloud {
+    // Add a Closed lower time frame candle to the bucket starting at `bucket_start`.
+    fn commit(&mut self, candle: &Candlestick, time_frame: TimeFrame, bucket_start: i64) {
+        match self.committed.as_mut() {
+            Some(committed) => merge(committed, candle),
+            None => self.committed = Some(start_bucket(candle, time_frame, bucket_start)),
+        }
+        self.bucket = Some(bucket_start);
+    }
+
+    // Commit the candle being built, if any, to the cloud. A provisional candle that never
+    // got its Closed version counts as it last stood.
+    fn close_current(&mut self, time_frame: TimeFrame) {
+        if let (Some(provisional), Some(bucket_start)) = (self.provisional.take(), self.bucket) {
+            self.commit(&provisional, time_frame, bucket_start);
+        }
+        self.bucket = None;
+        if let Some(mut closed) = self.committed.take() {
+            closed.state = CandlestickState::Closed;
+            self.last_result = self.cloud.calculate(&closed);
+        }
+    }
+}
+
+// Ichimoku clouds over several time frames at once, driven by candles of a lower time
+// frame that are aggregated into each of them as they come in.
+pub struct MultiTimeframeIchimoku {
+    clouds: HashMap<TimeFrame, TimeFrameCloud>,
+}
+
+impl MultiTimeframeIchimoku {
+    pub fn new(time_frames: &[TimeFrame], parameters: IchimokuCloudParameters) -> Self {
+        let clouds = time_frames
+            .iter()
+            .map(|&time_frame| {
+                let cloud = TimeFrameCloud {
+                    cloud: IchimokuCloud::new(parameters),
+                    bucket: None,
+                    committed: None,
+                    provisional: None,
+                    last_result: None,
+                };
+                (time_frame, cloud)
+            })
+            .collect();
+        Self { clouds }
+    }
+
+    // Add a candle to every time frame and return the latest result of each, i.e. the
+    // result of its last closed candle. A higher time frame candle closes with the closed
+    // candle that reaches the end of its bucket, or when a candle of a later bucket
+    // arrives, so its result changes once per bucket. Candles must be sorted, those
+    // without a timestamp or older than the current bucket are ignored.
+    pub fn update(
+        &mut self,
+        candle: &Candlestick,
+    ) -> HashMap<TimeFrame, Option<IchimokuCloudResult>> {
+        if let Some(timestamp) = candle.timestamp {
+            for (time_frame, tf_cloud) in self.clouds.iter_mut() {
+                Self::push(*time_frame, tf_cloud, candle, timestamp);
+            }
+        }
+
+        self.results()
+    }
+
+    // The latest result of every time frame without adding a candle.
+    pub fn results(&self) -> HashMap<TimeFrame, Option<IchimokuCloudResult>> {
+        self.clouds
+            .iter()
+            .map(|(&time_frame, tf_cloud)| (time_frame, tf_cloud.last_result.clone()))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        for tf_cloud in self.clouds.values_mut() {
+            tf_cloud.cloud.reset();
+            tf_cloud.bucket = None;
+            tf_cloud.committed = None;
+            tf_cloud.provisional = None;
+            tf_cloud.last_result = None;
+        }
+    }
+
+    fn push(
+        time_frame: TimeFrame,
+        tf_cloud: &mut TimeFrameCloud,
+        candle: &Candlestick,
+        timestamp: i64,
+    ) {
+        let bucket_start = time_frame.bucket_start(timestamp);
+        match tf_cloud.bucket {
+            Some(current) if current > bucket_start => return,
+            Some(current) if current < bucket_start => tf_cloud.close_current(time_frame),
+            _ => {}
+        }
+
+        // An Open candle superseded by a later lower time frame candle is final as it stands
+        if let Some(provisional) = tf_cloud.provisional.take() {
+            if provisional.timestamp != candle.timestamp {
+                tf_cloud.commit(&provisional, time_frame, bucket_start);
+            }
+        }
+
+        if candle.state == CandlestickState::Open {
+            tf_cloud.provisional = Some(candle.clone());
+            tf_cloud.bucket = Some(bucket_start);
+            return;
+        }
+        tf_cloud.commit(candle, time_frame, bucket_start);
+        if candle.time_frame.bucket_end(timestamp) >= time_frame.bucket_end(bucket_start) {
+            tf_cloud.close_current(time_frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::aggregate;
+    use crate::units::Volume;
+
+    fn minute(i: i64, high: f64, state: CandlestickState) -> Candlestick {
+        let close = 100.0 + i as f64;
+        Candlestick::new(
+            close,
+            high,
+            close - 1.0,
+            close,
+            TimeFrame::OneMinute,
+            Some(i * 60),
+            1,
+            Some(Volume::from(2.0)),
+            state,
+        )
+        .unwrap()
+    }
+
+    fn parameters() -> IchimokuCloudParameters {
+        IchimokuCloudParameters::new(1, 2, 3).unwrap()
+    }
+
+    #[test]
+    fn results_change_once_per_bucket() {
+        let mut clouds = MultiTimeframeIchimoku::new(
+            &[TimeFrame::FiveMinutes, TimeFrame::OneHour],
+            parameters(),
+        );
+        let mut previous = None;
+        let mut changes = Vec::new();
+        for i in 0..300 {
+            let results = clouds.update(&minute(i, 101.0 + i as f64, CandlestickState::Closed));
+            assert_eq!(results[&TimeFrame::FiveMinutes].is_some(), i >= 14);
+            let hourly = results[&TimeFrame::OneHour].clone();
+            if hourly != previous {
+                changes.push(i);
+            }
+            previous = hourly;
+        }
+        assert_eq!(changes, [179, 239, 299]);
+    }
+
+    #[test]
+    fn open_candles_are_replaced_by_their_closed_version() {
+        let closed: Vec<_> = (0..60)
+            .map(|i| minute(i, 101.0 + i as f64, CandlestickState::Closed))
+            .collect();
+        let mut reference = IchimokuCloud::new(parameters());
+        let expected: Vec<_> = aggregate(&closed, TimeFrame::FiveMinutes)
+            .unwrap()
+            .iter()
+            .map(|candle| reference.calculate(candle))
+            .collect();
+
+        let mut clouds = MultiTimeframeIchimoku::new(&[TimeFrame::FiveMinutes], parameters());
+        for (i, candle) in closed.iter().enumerate() {
+            // A spike that the Closed version revises away
+            clouds.update(&minute(i as i64, 500.0, CandlestickState::Open));
+            let results = clouds.update(candle);
+            if i % 5 == 4 {
+                assert_eq!(results[&TimeFrame::FiveMinutes], expected[i / 5]);
+            }
+        }
+    }
+
+    #[test]
+    fn an_open_candle_without_a_closed_version_counts_as_it_stands() {
+        let mut clouds = MultiTimeframeIchimoku::new(&[TimeFrame::FiveMinutes], parameters());
+        let mut reference = IchimokuCloud::new(parameters());
+        let mut expected = None;
+        for bucket in 0..4 {
+            // Only Open versions, each superseded by the next minute
+            let minutes: Vec<_> = (0..5)
+                .map(|i| minute(bucket * 5 + i, 150.0 + i as f64, CandlestickState::Open))
+                .collect();
+            for candle in &minutes {
+                clouds.update(candle);
+            }
+            assert_eq!(clouds.results()[&TimeFrame::FiveMinutes], expected);
+            let mut whole = aggregate(&minutes, TimeFrame::FiveMinutes).unwrap();
+            whole[0].state = CandlestickState::Closed;
+            expected = reference.calculate(&whole[0]);
+        }
+        clouds.update(&minute(20, 121.0, CandlestickState::Closed));
+        assert_eq!(clouds.results()[&TimeFrame::FiveMinutes], expected);
+    }
+}
@@ -0,0 +1,64 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+
+// Clean up candles from a raw feed: sort them by timestamp and keep one candle per
+// timestamp, the one with the most trades, or the first of them when that's a tie, which
+// also drops exact duplicates. Candles without a timestamp can't be placed, they go last
+// in the order they came in. Run this before `fill_gaps`.
+pub fn normalize(mut candles: Vec<Candlestick>) -> Vec<Candlestick> {
+    // A stable sort keeps the input order among equal timestamps and untimed candles
+    candles.sort_by_key(|candle| (candle.timestamp.is_none(), candle.timestamp));
+    candles.dedup_by(|later, kept| {
+        if later.timestamp.is_none() || later.timestamp != kept.timestamp {
+            return false;
+        }
+        if later.number_of_trades > kept.number_of_trades {
+            core::mem::swap(later, kept);
+        }
+        true
+    });
+    candles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn candle(timestamp: Option<i64>, number_of_trades: u32) -> Candlestick {
+        Candlestick {
+            timestamp,
+            number_of_trades,
+            ..Candlestick::ohlc(1.0, 1.0, 1.0, 1.0)
+        }
+    }
+
+    #[test]
+    fn sorts_and_keeps_the_busiest_candle_per_timestamp() {
+        let candles = vec![
+            candle(Some(3), 1),
+            candle(Some(1), 2),
+            candle(None, 7),
+            candle(Some(3), 5),
+            candle(Some(2), 1),
+            candle(Some(1), 2),
+            candle(None, 8),
+            candle(Some(3), 5),
+        ];
+        let normalized: Vec<_> = normalize(candles)
+            .iter()
+            .map(|candle| (candle.timestamp, candle.number_of_trades))
+            .collect();
+        assert_eq!(
+            normalized,
+            [
+                (Some(1), 2),
+                (Some(2), 1),
+                (Some(3), 5),
+                (None, 7),
+                (None, 8)
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+// On-balance volume. Candles without a volume count their number of trades instead.
+pub struct OnBalanceVolume {
+    previous_close: Option<f64>,
+    value: f64,
+}
+
+impl OnBalanceVolume {
+    pub fn new() -> Self {
+        Self {
+            previous_close: None,
+            value: 0.0,
+        }
+    }
+
+    // Adds the candle's volume when it closes above the previous close, subtracts it when
+    // it closes below and leaves the line unchanged otherwise. The first candle starts at 0.
+    pub fn update(&mut self, candle: &Candlestick) -> f64 {
+        if let Some(previous_close) = self.previous_close {
+            let volume = candle.volume_or_trades();
+            if candle.close > previous_close {
+                self.value += volume;
+            } else if candle.close < previous_close {
+                self.value -= volume;
+            }
+        }
+        self.previous_close = Some(candle.close);
+        round_to(self.value, 8)
+    }
+
+    pub fn reset(&mut self) {
+        self.previous_close = None;
+        self.value = 0.0;
+    }
+}
+
+impl Default for OnBalanceVolume {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for OnBalanceVolume {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        Some(OnBalanceVolume::update(self, candle))
+    }
+
+    fn reset(&mut self) {
+        OnBalanceVolume::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_and_subtracts_volume_with_the_direction() {
+        let with_volume = |close: f64, volume: f64| Candlestick {
+            volume: Some(volume),
+            ..Candlestick::ohlc(close, close, close, close)
+        };
+        let mut obv = OnBalanceVolume::new();
+        assert_eq!(obv.update(&with_volume(10.0, 100.0)), 0.0);
+        assert_eq!(obv.update(&with_volume(11.0, 50.0)), 50.0);
+        assert_eq!(obv.update(&with_volume(11.0, 70.0)), 50.0);
+        assert_eq!(obv.update(&with_volume(10.5, 20.0)), 30.0);
+        // Without a volume the number of trades stands in
+        assert_eq!(obv.update(&Candlestick::ohlc(12.0, 12.0, 12.0, 12.0)), 31.0);
+
+        obv.reset();
+        assert_eq!(obv.update(&with_volume(1.0, 5.0)), 0.0);
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::candlestick::Candlestick;
+use crate::utils::round_to;
+
+#[derive(Debug, PartialEq)]
+pub enum PairError {
+    // The two series don't have the same number of candles
+    LengthMismatch,
+    // The candles at this index don't have the same timestamp
+    TimestampMismatch(usize),
+}
+
+impl fmt::Display for PairError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PairError::LengthMismatch => write!(f, "the series have different lengths"),
+            PairError::TimestampMismatch(index) => {
+                write!(f, "the series aren't aligned at candle {}", index)
+            }
+        }
+    }
+}
+
+impl core::error::Error for PairError {}
+
+// The spread of a pair, `a.close - hedge_ratio * b.close` for every candle, to feed a
+// `RollingZScore` for mean reversion: a spread far from its average is expected to come
+// back. The series must be aligned, i.e. have the same length and the same timestamp at
+// every index, see `normalize` and `fill_gaps` to get there.
+pub fn spread(
+    a: &[Candlestick],
+    b: &[Candlestick],
+    hedge_ratio: f64,
+) -> Result<Vec<f64>, PairError> {
+    if a.len() != b.len() {
+        return Err(PairError::LengthMismatch);
+    }
+    a.iter()
+        .zip(b)
+        .enumerate()
+        .map(|(index, (a, b))| {
+            if a.timestamp != b.timestamp {
+                return Err(PairError::TimestampMismatch(index));
+            }
+            Ok(round_to(a.close - hedge_ratio * b.close, 8))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn candle(timestamp: i64, close: f64) -> Candlestick {
+        Candlestick {
+            timestamp: Some(timestamp),
+            ..Candlestick::ohlc(close, close, close, close)
+        }
+    }
+
+    #[test]
+    fn spread_of_aligned_series() {
+        let a = [candle(0, 10.0), candle(60, 12.0)];
+        let b = [candle(0, 4.0), candle(60, 5.5)];
+        assert_eq!(spread(&a, &b, 2.0), Ok(vec![2.0, 1.0]));
+        assert_eq!(spread(&a, &b[..1], 2.0), Err(PairError::LengthMismatch));
+        let b = [candle(0, 4.0), candle(120, 5.5)];
+        assert_eq!(spread(&a, &b, 2.0), Err(PairError::TimestampMismatch(1)));
+    }
+
+    #[test]
+    fn spread_z_score_flags_the_divergences() {
+        use crate::zscore::RollingZScore;
+
+        // `a` wiggles around twice `b` and breaks away from it twice
+        let b: Vec<_> = (0..35).map(|i| candle(i * 60, 50.0)).collect();
+        let a: Vec<_> = (0..35)
+            .map(|i| {
+                let close = match i {
+                    15 => 103.0,
+                    25 => 97.0,
+                    _ if i % 2 == 0 => 99.9,
+                    _ => 100.1,
+                };
+                candle(i * 60, close)
+            })
+            .collect();
+
+        let mut zscore = RollingZScore::new(10);
+        let signals: Vec<_> = spread(&a, &b, 2.0)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, spread)| Some((i, zscore.update(spread)?)))
+            .filter(|(_, z)| z.abs() > 2.0)
+            .map(|(i, z)| (i, z > 0.0))
+            .collect();
+        assert_eq!(signals, [(15, true), (25, false)]);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+pub struct ParabolicSar {
+    af_start: f64,
+    af_step: f64,
+    af_max: f64,
+    long: bool,
+    sar: f64,
+    // Extreme point of the current trend, the highest high of a long or lowest low of a short
+    extreme_point: f64,
+    acceleration_factor: f64,
+    // Highs and lows of the last two candles, most recent first
+    previous: [Option<(f64, f64)>; 2],
+}
+
+impl ParabolicSar {
+    pub fn new(af_start: f64, af_step: f64, af_max: f64) -> Self {
+        Self {
+            af_start,
+            af_step,
+            af_max,
+            long: true,
+            sar: 0.0,
+            extreme_point: 0.0,
+            acceleration_factor: af_start,
+            previous: [None, None],
+        }
+    }
+
+    // Whether the stop currently trails a long (rising) trend.
+    pub fn is_long(&self) -> bool {
+        self.long
+    }
+
+    // The stop and reverse level for the candle. The first candle starts a long trend with
+    // the stop at its low. The stop is never placed inside the range of the previous two
+    // candles, and the trend flips as soon as a candle crosses it.
+    pub fn update(&mut self, candle: &Candlestick) -> f64 {
+        let Some((previous_high, previous_low)) = self.previous[0] else {
+            self.long = true;
+            self.sar = candle.low;
+            self.extreme_point = candle.high;
+            self.acceleration_factor = self.af_start;
+            self.previous[0] = Some((candle.high, candle.low));
+            return round_to(self.sar, 8);
+        };
+        let (earlier_high, earlier_low) = self.previous[1].unwrap_or((previous_high, previous_low));
+
+        let mut sar = self.sar + self.acceleration_factor * (self.extreme_point - self.sar);
+        if self.long {
+            sar = sar.min(previous_low).min(earlier_low);
+            if candle.low < sar {
+                self.long = false;
+                sar = self.extreme_point;
+                self.extreme_point = candle.low;
+                self.acceleration_factor = self.af_start;
+            } else if candle.high > self.extreme_point {
+                self.extreme_point = candle.high;
+                self.acceleration_factor =
+                    (self.acceleration_factor + self.af_step).min(self.af_max);
+            }
+        } else {
+            sar = sar.max(previous_high).max(earlier_high);
+            if candle.high > sar {
+                self.long = true;
+                sar = self.extreme_point;
+                self.extreme_point = candle.high;
+                self.acceleration_factor = self.af_start;
+            } else if candle.low < self.extreme_point {
+                self.extreme_point = candle.low;
+                self.acceleration_factor =
+                    (self.acceleration_factor + self.af_step).min(self.af_max);
+            }
+        }
+
+        self.sar = sar;
+        self.previous = [Some((candle.high, candle.low)), self.previous[0]];
+        round_to(sar, 8)
+    }
+
+    pub fn reset(&mut self) {
+        self.long = true;
+        self.acceleration_factor = self.af_start;
+        self.previous = [None, None];
+    }
+}
+
+impl Default for ParabolicSar {
+    // Wilder's 0.02 start and step with a 0.2 maximum.
+    fn default() -> Self {
+        Self::new(0.02, 0.02, 0.2)
+    }
+}
+
+impl Indicator for ParabolicSar {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        Some(ParabolicSar::update(self, candle))
+    }
+
+    fn reset(&mut self) {
+        ParabolicSar::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn trails_a_rise_then_flips() {
+        let mut sar = ParabolicSar::default();
+        let stops: Vec<_> = [(11.0, 9.0), (12.0, 10.0), (13.0, 11.0), (14.0, 12.0)]
+            .iter()
+            .map(|&(high, low)| sar.update(&Candlestick::ohlc(low, high, low, high)))
+            .collect();
+        // The stop may not rise above the lows of the last two candles
+        assert_eq!(stops, [9.0, 9.0, 9.0, 9.24]);
+        assert!(sar.is_long());
+
+        // Breaking below the stop flips to short with the stop at the highest high
+        assert_eq!(sar.update(&Candlestick::ohlc(10.0, 10.0, 8.0, 8.0)), 14.0);
+        assert!(!sar.is_long());
+
+        sar.reset();
+        assert!(sar.is_long());
+        assert_eq!(sar.update(&Candlestick::ohlc(5.0, 6.0, 4.0, 5.0)), 4.0);
+    }
+
+    #[test]
+    fn stays_below_a_rising_market() {
+        let mut sar = ParabolicSar::default();
+        for i in 0..20 {
+            let price = 100.0 + i as f64;
+            let stop = sar.update(&Candlestick::ohlc(price, price + 1.0, price - 1.0, price));
+            assert!(stop <= price - 1.0, "{} at {}", stop, i);
+        }
+        assert!(sar.is_long());
+    }
+}
@@ -0,0 +1,344 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+
+// A candle whose body is smaller than `body_threshold` times its range, e.g. 0.1 for a
+// body under a tenth of the range. A candle without any range is a doji as well, since
+// open and close are equal.
+pub fn is_doji(candle: &Candlestick, body_threshold: f64) -> bool {
+    let range = candle.high - candle.low;
+    if range == 0.0 {
+        return true;
+    }
+    (candle.open - candle.close).abs() < body_threshold * range
+}
+
+// Direction of the market leading into a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trend {
+    Up,
+    Down,
+    Sideways,
+}
+
+// A hammer shaped candle is bullish after a downtrend and bearish, a hanging man, after
+// an uptrend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HammerKind {
+    Hammer,
+    HangingMan,
+}
+
+// A small body at the top of the range: the body takes at most a third of the range, the
+// lower shadow is at least twice the body and the upper shadow at most a tenth of the
+// range.
+pub fn is_hammer(candle: &Candlestick) -> bool {
+    let range = candle.high - candle.low;
+    if range == 0.0 {
+        return false;
+    }
+    let body = (candle.open - candle.close).abs();
+    let lower_shadow = candle.open.min(candle.close) - candle.low;
+    let upper_shadow = candle.high - candle.open.max(candle.close);
+
+    body <= range / 3.0 && lower_shadow >= 2.0 * body && upper_shadow <= range / 10.0
+}
+
+// The inverse of a hammer: a small body at the bottom of the range with a long upper
+// shadow, at least twice the body, and a lower shadow at most a tenth of the range.
+pub fn is_shooting_star(candle: &Candlestick) -> bool {
+    let range = candle.high - candle.low;
+    if range == 0.0 {
+        return false;
+    }
+    let body = (candle.open - candle.close).abs();
+    let lower_shadow = candle.open.min(candle.close) - candle.low;
+    let upper_shadow = candle.high - candle.open.max(candle.close);
+
+    body <= range / 3.0 && upper_shadow >= 2.0 * body && lower_shadow <= range / 10.0
+}
+
+// Tell a hammer from a hanging man by the trend before it. Returns None when the candle
+// isn't hammer shaped or the trend is sideways.
+pub fn classify_hammer(prev_trend: Trend, candle: &Candlestick) -> Option<HammerKind> {
+    if !is_hammer(candle) {
+        return None;
+    }
+    match prev_trend {
+        Trend::Down => Some(HammerKind::Hammer),
+        Trend::Up => Some(HammerKind::HangingMan),
+        Trend::Sideways => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternSignal {
+    Doji,
+    Hammer,
+    ShootingStar,
+    BullishEngulfing,
+    BearishEngulfing,
+    MorningStar,
+    EveningStar,
+}
+
+// A candle whose body covers the whole body of the previous candle in the opposite
+// direction: an up candle engulfing a down candle is bullish, the mirror image bearish.
+pub fn engulfing(prev: &Candlestick, curr: &Candlestick) -> Option<PatternSignal> {
+    let prev_body = (prev.open - prev.close).abs();
+    let curr_body = (curr.open - curr.close).abs();
+    if curr_body <= prev_body {
+        return None;
+    }
+
+    if prev.close < prev.open
+        && curr.close > curr.open
+        && curr.open <= prev.close
+        && curr.close >= prev.open
+    {
+        Some(PatternSignal::BullishEngulfing)
+    } else if prev.close > prev.open
+        && curr.close < curr.open
+        && curr.open >= prev.close
+        && curr.close <= prev.open
+    {
+        Some(PatternSignal::BearishEngulfing)
+    } else {
+        None
+    }
+}
+
+// A long candle has a body of at least half its range.
+fn is_long(candle: &Candlestick) -> bool {
+    let range = candle.high - candle.low;
+    range > 0.0 && (candle.open - candle.close).abs() >= range / 2.0
+}
+
+// Three candle bullish reversal: a long down candle, a star whose body is at most a third
+// of the first body and gaps below both neighbouring bodies, then a long up candle closing
+// above the middle of the first body. `gap_tolerance` is how far, in price, the star's
+// body may overlap the other bodies and still count as a gap, since markets that trade
+// around the clock rarely gap cleanly. Use 0.0 for a strict gap.
+pub fn morning_star(
+    c1: &Candlestick,
+    c2: &Candlestick,
+    c3: &Candlestick,
+    gap_tolerance: f64,
+) -> bool {
+    let first_body = c1.open - c1.close;
+    let star_top = c2.open.max(c2.close);
+
+    c1.close < c1.open
+        && is_long(c1)
+        && (c2.open - c2.close).abs() <= first_body / 3.0
+        && star_top <= c1.close + gap_tolerance
+        && c3.close > c3.open
+        && is_long(c3)
+        && star_top <= c3.open + gap_tolerance
+        && c3.close > c1.close + first_body / 2.0
+}
+
+// The bearish mirror of `morning_star`: a long up candle, a star gapping above both
+// neighbouring bodies, then a long down candle closing below the middle of the first body.
+pub fn evening_star(
+    c1: &Candlestick,
+    c2: &Candlestick,
+    c3: &Candlestick,
+    gap_tolerance: f64,
+) -> bool {
+    let first_body = c1.close - c1.open;
+    let star_bottom = c2.open.min(c2.close);
+
+    c1.close > c1.open
+        && is_long(c1)
+        && (c2.open - c2.close).abs() <= first_body / 3.0
+        && star_bottom >= c1.close - gap_tolerance
+        && c3.close < c3.open
+        && is_long(c3)
+        && star_bottom >= c3.open - gap_tolerance
+        && c3.close < c1.close - first_body / 2.0
+}
+
+// Looks for a pattern ending at `index`. Multi-candle detectors look back from there and
+// find nothing when there aren't enough candles before it.
+pub trait PatternDetector {
+    fn detect(&self, candles: &[Candlestick], index: usize) -> Option<PatternSignal>;
+}
+
+pub struct DojiDetector {
+    pub body_threshold: f64,
+}
+
+impl PatternDetector for DojiDetector {
+    fn detect(&self, candles: &[Candlestick], index: usize) -> Option<PatternSignal> {
+        is_doji(&candles[index], self.body_threshold).then_some(PatternSignal::Doji)
+    }
+}
+
+pub struct HammerDetector;
+
+impl PatternDetector for HammerDetector {
+    fn detect(&self, candles: &[Candlestick], index: usize) -> Option<PatternSignal> {
+        is_hammer(&candles[index]).then_some(PatternSignal::Hammer)
+    }
+}
+
+pub struct ShootingStarDetector;
+
+impl PatternDetector for ShootingStarDetector {
+    fn detect(&self, candles: &[Candlestick], index: usize) -> Option<PatternSignal> {
+        is_shooting_star(&candles[index]).then_some(PatternSignal::ShootingStar)
+    }
+}
+
+pub struct EngulfingDetector;
+
+impl PatternDetector for EngulfingDetector {
+    fn detect(&self, candles: &[Candlestick], index: usize) -> Option<PatternSignal> {
+        let previous = candles.get(index.checked_sub(1)?)?;
+        engulfing(previous, &candles[index])
+    }
+}
+
+pub struct StarDetector {
+    pub gap_tolerance: f64,
+}
+
+impl PatternDetector for StarDetector {
+    fn detect(&self, candles: &[Candlestick], index: usize) -> Option<PatternSignal> {
+        let first = index.checked_sub(2)?;
+        let (c1, c2, c3) = (&candles[first], &candles[first + 1], &candles[index]);
+        if morning_star(c1, c2, c3, self.gap_tolerance) {
+            Some(PatternSignal::MorningStar)
+        } else if evening_star(c1, c2, c3, self.gap_tolerance) {
+            Some(PatternSignal::EveningStar)
+        } else {
+            None
+        }
+    }
+}
+
+// Doji (with a body under a tenth of the range), hammer, shooting star, engulfing and
+// morning/evening star with a strict gap.
+pub fn default_detectors() -> Vec<Box<dyn PatternDetector>> {
+    vec![
+        Box::new(DojiDetector {
+            body_threshold: 0.1,
+        }),
+        Box::new(HammerDetector),
+        Box::new(ShootingStarDetector),
+        Box::new(EngulfingDetector),
+        Box::new(StarDetector { gap_tolerance: 0.0 }),
+    ]
+}
+
+// Run the default detectors over every candle, see `scan_with`.
+pub fn scan(candles: &[Candlestick]) -> Vec<(usize, PatternSignal)> {
+    scan_with(candles, &default_detectors())
+}
+
+// Every pattern the detectors find, as the index of the candle completing it and the
+// pattern, in candle order. A candle can complete more than one pattern.
+pub fn scan_with(
+    candles: &[Candlestick],
+    detectors: &[Box<dyn PatternDetector>],
+) -> Vec<(usize, PatternSignal)> {
+    let mut found = Vec::new();
+    for index in 0..candles.len() {
+        for detector in detectors {
+            if let Some(signal) = detector.detect(candles, index) {
+                found.push((index, signal));
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_candle_shapes() {
+        let hammer = Candlestick::ohlc(9.5, 10.0, 7.0, 10.0);
+        let shooting_star = Candlestick::ohlc(7.5, 10.0, 7.0, 7.0);
+        assert!(is_hammer(&hammer) && !is_shooting_star(&hammer));
+        assert!(is_shooting_star(&shooting_star) && !is_hammer(&shooting_star));
+
+        assert!(is_doji(&Candlestick::ohlc(5.0, 6.0, 4.0, 5.05), 0.1));
+        assert!(!is_doji(&Candlestick::ohlc(5.0, 6.0, 4.0, 5.5), 0.1));
+        assert!(is_doji(&Candlestick::ohlc(5.0, 5.0, 5.0, 5.0), 0.1));
+
+        assert_eq!(
+            classify_hammer(Trend::Down, &hammer),
+            Some(HammerKind::Hammer)
+        );
+        assert_eq!(
+            classify_hammer(Trend::Up, &hammer),
+            Some(HammerKind::HangingMan)
+        );
+        assert_eq!(classify_hammer(Trend::Sideways, &hammer), None);
+        assert_eq!(classify_hammer(Trend::Down, &shooting_star), None);
+    }
+
+    #[test]
+    fn engulfing_needs_a_larger_opposite_body() {
+        let down = Candlestick::ohlc(10.0, 10.5, 8.5, 9.0);
+        let up = Candlestick::ohlc(8.8, 11.0, 8.5, 10.5);
+        assert_eq!(engulfing(&down, &up), Some(PatternSignal::BullishEngulfing));
+        let down = Candlestick::ohlc(10.2, 11.0, 8.5, 8.8);
+        let up = Candlestick::ohlc(9.0, 10.5, 8.5, 10.0);
+        assert_eq!(engulfing(&up, &down), Some(PatternSignal::BearishEngulfing));
+        assert_eq!(engulfing(&down, &up), None);
+        assert_eq!(engulfing(&up, &up), None);
+    }
+
+    #[test]
+    fn stars_gap_away_from_both_neighbours() {
+        let morning = [
+            Candlestick::ohlc(12.0, 12.2, 9.8, 10.0),
+            Candlestick::ohlc(9.5, 9.8, 9.2, 9.6),
+            Candlestick::ohlc(9.8, 12.0, 9.7, 11.5),
+        ];
+        assert!(morning_star(&morning[0], &morning[1], &morning[2], 0.0));
+        assert_eq!(scan(&morning), [(2, PatternSignal::MorningStar)]);
+
+        let evening = [
+            Candlestick::ohlc(10.0, 12.2, 9.8, 12.0),
+            Candlestick::ohlc(12.5, 12.8, 12.2, 12.4),
+            Candlestick::ohlc(12.2, 12.3, 10.0, 10.5),
+        ];
+        assert!(evening_star(&evening[0], &evening[1], &evening[2], 0.0));
+        assert!(!morning_star(&evening[0], &evening[1], &evening[2], 0.0));
+
+        // A star overlapping the third body only counts within the tolerance
+        let star = Candlestick::ohlc(9.5, 10.0, 9.2, 9.9);
+        assert!(!morning_star(&morning[0], &star, &morning[2], 0.0));
+        assert!(morning_star(&morning[0], &star, &morning[2], 0.1));
+    }
+
+    #[test]
+    fn scan_with_custom_detectors() {
+        let candles = [
+            Candlestick::ohlc(5.0, 6.0, 4.0, 5.0),
+            Candlestick::ohlc(9.5, 10.0, 7.0, 10.0),
+        ];
+        let detectors: Vec<Box<dyn PatternDetector>> = vec![Box::new(HammerDetector)];
+        assert_eq!(
+            scan_with(&candles, &detectors),
+            [(1, PatternSignal::Hammer)]
+        );
+        assert_eq!(
+            scan(&candles),
+            [(0, PatternSignal::Doji), (1, PatternSignal::Hammer)]
+        );
+        // Multi-candle detectors find nothing at the start
+        assert_eq!(EngulfingDetector.detect(&candles, 0), None);
+        assert_eq!(
+            StarDetector { gap_tolerance: 0.0 }.detect(&candles, 1),
+            None
+        );
+    }
+}
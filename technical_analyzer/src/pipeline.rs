@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::aroon::AroonResult;
+use crate::bollinger::BollingerResult;
+use crate::candlestick::Candlestick;
+use crate::dmi::DmiResult;
+use crate::donchian::DonchianResult;
+use crate::ichimoku::IchimokuCloudResult;
+use crate::indicator::Indicator;
+use crate::keltner::KeltnerResult;
+use crate::macd::MacdResult;
+use crate::squeeze::SqueezeState;
+use crate::standard_error::StandardErrorResult;
+use crate::stochastic::StochResult;
+
+// The output of any indicator, so a pipeline can collect them in one map.
+pub enum IndicatorValue {
+    Scalar(f64),
+    Aroon(AroonResult),
+    Bollinger(BollingerResult),
+    Dmi(DmiResult),
+    Donchian(DonchianResult),
+    Ichimoku(IchimokuCloudResult),
+    Keltner(KeltnerResult),
+    Macd(MacdResult),
+    Squeeze(SqueezeState),
+    StandardError(StandardErrorResult),
+    Stochastic(StochResult),
+}
+
+impl IndicatorValue {
+    // The value of an indicator with a single number as output.
+    pub fn scalar(&self) -> Option<f64> {
+        match self {
+            IndicatorValue::Scalar(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl From<f64> for IndicatorValue {
+    fn from(output: f64) -> Self {
+        IndicatorValue::Scalar(output)
+    }
+}
+
+impl From<AroonResult> for IndicatorValue {
+    fn from(output: AroonResult) -> Self {
+        IndicatorValue::Aroon(output)
+    }
+}
+
+impl From<BollingerResult> for IndicatorValue {
+    fn from(output: BollingerResult) -> Self {
+        IndicatorValue::Bollinger(output)
+    }
+}
+
+impl From<DmiResult> for IndicatorValue {
+    fn from(output: DmiResult) -> Self {
+        IndicatorValue::Dmi(output)
+    }
+}
+
+impl From<DonchianResult> for IndicatorValue {
+    fn from(output: DonchianResult) -> Self {
+        IndicatorValue::Donchian(output)
+    }
+}
+
+impl From<IchimokuCloudResult> for IndicatorValue {
+    fn from(output: IchimokuCloudResult) -> Self {
+        IndicatorValue::Ichimoku(output)
+    }
+}
+
+impl From<KeltnerResult> for IndicatorValue {
+    fn from(output: KeltnerResult) -> Self {
+        IndicatorValue::Keltner(output)
+    }
+}
+
+impl From<MacdResult> for IndicatorValue {
+    fn from(output: MacdResult) -> Self {
+        IndicatorValue::Macd(output)
+    }
+}
+
+impl From<SqueezeState> for IndicatorValue {
+    fn from(output: SqueezeState) -> Self {
+        IndicatorValue::Squeeze(output)
+    }
+}
+
+impl From<StandardErrorResult> for IndicatorValue {
+    fn from(output: StandardErrorResult) -> Self {
+        IndicatorValue::StandardError(output)
+    }
+}
+
+impl From<StochResult> for IndicatorValue {
+    fn from(output: StochResult) -> Self {
+        IndicatorValue::Stochastic(output)
+    }
+}
+
+// An indicator with its output converted to an `IndicatorValue`.
+trait PipelineStage {
+    fn update(&mut self, candle: &Candlestick) -> Option<IndicatorValue>;
+
+    fn reset(&mut self);
+
+    fn bars_until_ready(&self) -> usize;
+}
+
+impl<I> PipelineStage for I
+where
+    I: Indicator,
+    I::Output: Into<IndicatorValue>,
+{
+    fn update(&mut self, candle: &Candlestick) -> Option<IndicatorValue> {
+        Indicator::update(self, candle).map(Into::into)
+    }
+
+    fn reset(&mut self) {
+        Indicator::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        Indicator::bars_until_ready(self)
+    }
+}
+
+// Drives several named indicators from one candle stream.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<(String, Box<dyn PipelineStage>)>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add an indicator whose outputs show up under `name`. Names should be unique, the
+    // output of a later indicator replaces that of an earlier one with the same name.
+    pub fn add<I>(&mut self, name: &str, indicator: Box<I>) -> &mut Self
+    where
+        I: Indicator + 'static,
+        I::Output: Into<IndicatorValue>,
+    {
+        self.stages.push((name.to_string(), indicator));
+        self
+    }
+
+    // Feed the candle to every indicator, in the order they were added. Indicators that
+    // are still warming up are missing from the map.
+    pub fn update(&mut self, candle: &Candlestick) -> HashMap<String, IndicatorValue> {
+        self.stages
+            .iter_mut()
+            .filter_map(|(name, stage)| stage.update(candle).map(|value| (name.clone(), value)))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        for (_, stage) in self.stages.iter_mut() {
+            stage.reset();
+        }
+    }
+
+    // Candles until every indicator returns values, the longest warm-up of them.
+    pub fn bars_until_ready(&self) -> usize {
+        self.stages
+            .iter()
+            .map(|(_, stage)| stage.bars_until_ready())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aroon::Aroon;
+    use crate::sma::SimpleMovingAverage;
+
+    #[test]
+    fn collects_the_ready_outputs_by_name() {
+        let mut pipeline = Pipeline::new();
+        assert!(pipeline.is_empty());
+        pipeline
+            .add("sma", Box::new(SimpleMovingAverage::new(3)))
+            .add("aroon", Box::new(Aroon::new(1)));
+        assert_eq!(pipeline.len(), 2);
+        assert_eq!(pipeline.bars_until_ready(), 3);
+
+        let candle = |close: f64| Candlestick::ohlc(close, close, close, close);
+        assert!(pipeline.update(&candle(1.0)).is_empty());
+        let values = pipeline.update(&candle(2.0));
+        assert_eq!(values.len(), 1);
+        assert!(matches!(values["aroon"], IndicatorValue::Aroon(_)));
+        assert_eq!(values["aroon"].scalar(), None);
+
+        let values = pipeline.update(&candle(3.0));
+        assert_eq!(values["sma"].scalar(), Some(2.0));
+        assert_eq!(pipeline.bars_until_ready(), 0);
+
+        pipeline.reset();
+        assert_eq!(pipeline.bars_until_ready(), 3);
+    }
+}
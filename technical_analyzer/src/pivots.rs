@@ -0,0 +1,111 @@
+use crate::candlestick::Candlestick;
+use crate::utils::round_to;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PivotMethod {
+    // Floor trader pivots, levels from the pivot and the previous range
+    Classic,
+    // Levels at the 0.382, 0.618 and 1.0 Fibonacci ratios of the range around the pivot
+    Fibonacci,
+    // Levels at 1.1/12, 1.1/6 and 1.1/4 of the range around the previous close
+    Camarilla,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PivotLevels {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+// Support and resistance levels for the current period from the high, low and close of
+// the previous one, e.g. yesterday's daily candle. The pivot is (high + low + close) / 3
+// for every method.
+pub fn pivot_points(prev: &Candlestick, method: PivotMethod) -> PivotLevels {
+    let (high, low, close) = (prev.high, prev.low, prev.close);
+    let pivot = (high + low + close) / 3.0;
+    let range = high - low;
+
+    let (r1, r2, r3, s1, s2, s3) = match method {
+        PivotMethod::Classic => (
+            2.0 * pivot - low,
+            pivot + range,
+            high + 2.0 * (pivot - low),
+            2.0 * pivot - high,
+            pivot - range,
+            low - 2.0 * (high - pivot),
+        ),
+        PivotMethod::Fibonacci => (
+            pivot + 0.382 * range,
+            pivot + 0.618 * range,
+            pivot + range,
+            pivot - 0.382 * range,
+            pivot - 0.618 * range,
+            pivot - range,
+        ),
+        PivotMethod::Camarilla => (
+            close + range * 1.1 / 12.0,
+            close + range * 1.1 / 6.0,
+            close + range * 1.1 / 4.0,
+            close - range * 1.1 / 12.0,
+            close - range * 1.1 / 6.0,
+            close - range * 1.1 / 4.0,
+        ),
+    };
+
+    PivotLevels {
+        pivot: round_to(pivot, 8),
+        r1: round_to(r1, 8),
+        r2: round_to(r2, 8),
+        r3: round_to(r3, 8),
+        s1: round_to(s1, 8),
+        s2: round_to(s2, 8),
+        s3: round_to(s3, 8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(method: PivotMethod) -> [f64; 7] {
+        let levels = pivot_points(&Candlestick::ohlc(9.0, 12.0, 8.0, 10.0), method);
+        [
+            levels.s3,
+            levels.s2,
+            levels.s1,
+            levels.pivot,
+            levels.r1,
+            levels.r2,
+            levels.r3,
+        ]
+    }
+
+    #[test]
+    fn levels_around_the_previous_range() {
+        assert_eq!(
+            levels(PivotMethod::Classic),
+            [4.0, 6.0, 8.0, 10.0, 12.0, 14.0, 16.0]
+        );
+        assert_eq!(
+            levels(PivotMethod::Fibonacci),
+            [6.0, 7.528, 8.472, 10.0, 11.528, 12.472, 14.0]
+        );
+        assert_eq!(
+            levels(PivotMethod::Camarilla),
+            [
+                8.9,
+                9.26666667,
+                9.63333333,
+                10.0,
+                10.36666667,
+                10.73333333,
+                11.1
+            ]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+use crate::ichimoku::IchimokuCloudResult;
+
+// One row of indicator output with plain fields only, for tabular tools like pandas or
+// Arrow. With the serde feature it serializes as a flat object, and a list of them as
+// records. The Ichimoku values are None while the cloud is warming up.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndicatorRecord {
+    pub timestamp: Option<i64>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: Option<f64>,
+    pub tenkan_sen: Option<f64>,
+    pub kijun_sen: Option<f64>,
+    pub senkou_span_a: Option<f64>,
+    pub senkou_span_b: Option<f64>,
+    pub chikou_span: Option<f64>,
+}
+
+// Flatten candles and their Ichimoku results, as returned by `IchimokuCloud::initialize`,
+// into one record each.
+pub fn to_records(results: &[(&Candlestick, Option<IchimokuCloudResult>)]) -> Vec<IndicatorRecord> {
+    results
+        .iter()
+        .map(|(candle, result)| IndicatorRecord {
+            timestamp: candle.timestamp,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+            tenkan_sen: result.as_ref().map(|result| result.tenkan_sen),
+            kijun_sen: result.as_ref().map(|result| result.kijun_sen),
+            senkou_span_a: result.as_ref().map(|result| result.senkou_span_a),
+            senkou_span_b: result.as_ref().map(|result| result.senkou_span_b),
+            chikou_span: result.as_ref().map(|result| result.chikou_span),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ichimoku::{IchimokuCloud, IchimokuCloudParameters};
+
+    #[test]
+    fn flattens_candles_and_results() {
+        let candles: Vec<_> = (0..4)
+            .map(|i| Candlestick {
+                timestamp: Some(60 * i),
+                volume: Some(10.0),
+                ..Candlestick::ohlc(2.0, 3.0, 1.0, 2.5)
+            })
+            .collect();
+        let mut cloud = IchimokuCloud::new(IchimokuCloudParameters::new(1, 2, 3).unwrap());
+        let results = cloud.initialize(&candles);
+        let records = to_records(&results);
+
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].tenkan_sen, None);
+        assert_eq!(records[0].chikou_span, None);
+        assert_eq!(
+            (records[1].timestamp, records[1].volume),
+            (Some(60), Some(10.0))
+        );
+        let last = results[3].1.as_ref().unwrap();
+        assert_eq!(
+            records[3],
+            IndicatorRecord {
+                timestamp: Some(180),
+                open: 2.0,
+                high: 3.0,
+                low: 1.0,
+                close: 2.5,
+                volume: Some(10.0),
+                tenkan_sen: Some(last.tenkan_sen),
+                kijun_sen: Some(last.kijun_sen),
+                senkou_span_a: Some(last.senkou_span_a),
+                senkou_span_b: Some(last.senkou_span_b),
+                chikou_span: Some(2.5),
+            }
+        );
+    }
+}
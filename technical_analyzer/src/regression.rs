@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+use crate::utils::{round_to, sqrt};
+
+// Least squares fit of a line through the closes, with the index of each close as x.
+// Returns the slope per bar and the intercept at index 0. Fewer than two closes give a
+// flat line through their average, 0 without any.
+pub fn linear_regression(closes: &[f64]) -> (f64, f64) {
+    if closes.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = closes.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = closes.iter().sum::<f64>() / n;
+
+    let (covariance, variance) =
+        closes
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+                let dx = x as f64 - mean_x;
+                (covariance + dx * (y - mean_y), variance + dx * dx)
+            });
+    if variance == 0.0 {
+        return (0.0, round_to(mean_y, 8));
+    }
+
+    let slope = covariance / variance;
+    (round_to(slope, 8), round_to(mean_y - slope * mean_x, 8))
+}
+
+// A regression line with bands `offset` above and below it. A rising slope means an
+// uptrend, closes near a band are stretched away from it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionChannel {
+    pub slope: f64,
+    pub intercept: f64,
+    // Distance of the bands from the line
+    pub offset: f64,
+}
+
+impl RegressionChannel {
+    // The line at the candle with this index in the fitted slice.
+    pub fn middle(&self, index: usize) -> f64 {
+        round_to(self.intercept + self.slope * index as f64, 8)
+    }
+
+    pub fn upper(&self, index: usize) -> f64 {
+        round_to(self.middle(index) + self.offset, 8)
+    }
+
+    pub fn lower(&self, index: usize) -> f64 {
+        round_to(self.middle(index) - self.offset, 8)
+    }
+}
+
+// Fit a line through the candles' closes and put the bands `std_mult` population standard
+// deviations of the closes around the line away from it. None without candles.
+pub fn regression_channel(candles: &[Candlestick], std_mult: f64) -> Option<RegressionChannel> {
+    if candles.is_empty() {
+        return None;
+    }
+    let closes: Vec<f64> = candles.iter().map(|candle| candle.close).collect();
+    let (slope, intercept) = linear_regression(&closes);
+
+    let variance = closes
+        .iter()
+        .enumerate()
+        .map(|(x, close)| {
+            let residual = close - (intercept + slope * x as f64);
+            residual * residual
+        })
+        .sum::<f64>()
+        / closes.len() as f64;
+
+    Some(RegressionChannel {
+        slope,
+        intercept,
+        offset: round_to(std_mult * sqrt(variance), 8),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_a_line_through_the_closes() {
+        assert_eq!(linear_regression(&[1.0, 3.0, 5.0, 7.0]), (2.0, 1.0));
+        assert_eq!(linear_regression(&[1.0, 3.0, 2.0, 4.0]), (0.8, 1.3));
+        assert_eq!(linear_regression(&[5.0]), (0.0, 5.0));
+        assert_eq!(linear_regression(&[]), (0.0, 0.0));
+    }
+
+    #[test]
+    fn bands_are_standard_deviations_of_the_residuals() {
+        let candles: Vec<_> = [1.0, 3.0, 2.0, 4.0]
+            .iter()
+            .map(|&close| Candlestick::ohlc(close, close, close, close))
+            .collect();
+        // Residuals of -0.3, 0.9, -0.9 and 0.3
+        let channel = regression_channel(&candles, 2.0).unwrap();
+        assert_eq!(channel.offset, 1.34164079);
+        assert_eq!(channel.middle(2), 2.9);
+        assert_eq!(channel.upper(2), 4.24164079);
+        assert_eq!(channel.lower(2), 1.55835921);
+        assert_eq!(regression_channel(&[], 2.0), None);
+    }
+}
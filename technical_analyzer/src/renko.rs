@@ -0,0 +1,150 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+
+// Most bricks `renko` builds, so a tiny brick size over a large move can't exhaust memory.
+pub const MAX_BRICKS: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenkoBrick {
+    pub open: f64,
+    pub close: f64,
+    pub direction: Direction,
+    // Timestamp of the candle whose close completed the brick
+    pub timestamp: i64,
+}
+
+// Build Renko bricks of `brick_size` from the candles' closes, starting at the first
+// close. A move adds as many bricks as it spans. Continuing the trend takes one brick
+// beyond the last one, reversing it takes two, so the first brick of a reversal starts at
+// the far end of the last brick. Candles without a timestamp are skipped. A brick size too
+// small to change the price level it's added to ends the bricks there, and so does
+// reaching `MAX_BRICKS`.
+pub fn renko(candles: &[Candlestick], brick_size: f64) -> Vec<RenkoBrick> {
+    let mut bricks: Vec<RenkoBrick> = Vec::new();
+    if brick_size <= 0.0 {
+        return bricks;
+    }
+
+    let mut base: Option<f64> = None;
+    for candle in candles {
+        let Some(timestamp) = candle.timestamp else {
+            continue;
+        };
+        let close = candle.close;
+        let start = *base.get_or_insert(close);
+
+        // The level a new up or down brick would start from
+        let (up_from, down_from) = match bricks.last() {
+            Some(last) if last.direction == Direction::Up => (last.close, last.open),
+            Some(last) => (last.open, last.close),
+            None => (start, start),
+        };
+        if up_from + brick_size == up_from || down_from - brick_size == down_from {
+            break;
+        }
+
+        let (from, step, direction) = if close >= up_from + brick_size {
+            (up_from, brick_size, Direction::Up)
+        } else if close <= down_from - brick_size {
+            (down_from, -brick_size, Direction::Down)
+        } else {
+            continue;
+        };
+        // Truncating the positive ratio floors it, at least one brick was spanned
+        let count = (((close - from) / step) as usize).max(1);
+        let count = count.min(MAX_BRICKS - bricks.len());
+        bricks.extend((0..count).map(|i| RenkoBrick {
+            open: from + i as f64 * step,
+            close: from + (i + 1) as f64 * step,
+            direction,
+            timestamp,
+        }));
+        if bricks.len() == MAX_BRICKS {
+            break;
+        }
+    }
+
+    bricks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::candlestick::{CandlestickState, TimeFrame};
+
+    fn closes(closes: &[f64]) -> Vec<Candlestick> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| {
+                Candlestick::new(
+                    close,
+                    close,
+                    close,
+                    close,
+                    TimeFrame::OneDay,
+                    Some(i as i64 * 86_400),
+                    1,
+                    None,
+                    CandlestickState::Closed,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn summary(bricks: &[RenkoBrick]) -> Vec<(f64, f64, Direction, i64)> {
+        bricks
+            .iter()
+            .map(|brick| (brick.open, brick.close, brick.direction, brick.timestamp))
+            .collect()
+    }
+
+    #[test]
+    fn a_move_adds_every_brick_it_spans() {
+        let bricks = renko(&closes(&[100.0, 101.0, 103.5]), 1.0);
+        assert_eq!(
+            summary(&bricks),
+            [
+                (100.0, 101.0, Direction::Up, 86_400),
+                (101.0, 102.0, Direction::Up, 172_800),
+                (102.0, 103.0, Direction::Up, 172_800),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_reversal_takes_two_bricks() {
+        // 101.5 is within a brick of the top, 101 reverses from the bottom of the last brick
+        let bricks = renko(&closes(&[100.0, 102.0, 101.5, 100.9, 99.0]), 1.0);
+        assert_eq!(
+            summary(&bricks),
+            [
+                (100.0, 101.0, Direction::Up, 86_400),
+                (101.0, 102.0, Direction::Up, 86_400),
+                (101.0, 100.0, Direction::Down, 345_600),
+                (100.0, 99.0, Direction::Down, 345_600),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unusable_brick_sizes() {
+        assert!(renko(&closes(&[100.0, 200.0]), 0.0).is_empty());
+        assert!(renko(&closes(&[1000.0, 1001.0]), 1e-14).is_empty());
+    }
+
+    #[test]
+    fn brick_count_is_capped() {
+        let bricks = renko(&closes(&[1.0, 1e6, 2e6]), 1e-6);
+        assert_eq!(bricks.len(), MAX_BRICKS);
+        assert!(bricks.iter().all(|brick| brick.timestamp == 86_400));
+    }
+}
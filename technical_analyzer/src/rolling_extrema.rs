@@ -0,0 +1,192 @@
+use alloc::collections::VecDeque;
+
+// Minimum and maximum of the last `window` values, kept in monotonic deques so that each
+// push is amortized O(1) instead of rescanning the window.
+pub struct RollingExtrema {
+    window: usize,
+    num_pushed: usize,
+    // (index, value) candidates for the maximum, values decreasing from front to back, so
+    // every entry is the maximum of the values pushed since it.
+    maxima: VecDeque<(usize, f64)>,
+    // Same for the minimum, values increasing from front to back.
+    minima: VecDeque<(usize, f64)>,
+}
+
+impl RollingExtrema {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            num_pushed: 0,
+            maxima: VecDeque::new(),
+            minima: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        let index = self.num_pushed;
+        self.num_pushed += 1;
+
+        while self.maxima.back().is_some_and(|&(_, max)| max <= value) {
+            self.maxima.pop_back();
+        }
+        self.maxima.push_back((index, value));
+        while self.minima.back().is_some_and(|&(_, min)| min >= value) {
+            self.minima.pop_back();
+        }
+        self.minima.push_back((index, value));
+
+        let oldest = self.num_pushed.saturating_sub(self.window);
+        while self.maxima.front().is_some_and(|&(i, _)| i < oldest) {
+            self.maxima.pop_front();
+        }
+        while self.minima.front().is_some_and(|&(i, _)| i < oldest) {
+            self.minima.pop_front();
+        }
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.maxima.front().map(|&(_, max)| max)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.minima.front().map(|&(_, min)| min)
+    }
+
+    // How many values were pushed after the maximum, 0 when it is the latest one. Ties go
+    // to the most recent value.
+    pub fn max_age(&self) -> Option<usize> {
+        self.maxima.front().map(|&(i, _)| self.num_pushed - 1 - i)
+    }
+
+    // Same for the minimum.
+    pub fn min_age(&self) -> Option<usize> {
+        self.minima.front().map(|&(i, _)| self.num_pushed - 1 - i)
+    }
+
+    // The maximum the window would have if `value` was pushed, without pushing it.
+    pub fn max_with(&self, value: f64) -> f64 {
+        let oldest = (self.num_pushed + 1).saturating_sub(self.window);
+        self.maxima
+            .iter()
+            .find(|&&(i, _)| i >= oldest)
+            .map_or(value, |&(_, max)| max.max(value))
+    }
+
+    // The minimum the window would have if `value` was pushed, without pushing it.
+    pub fn min_with(&self, value: f64) -> f64 {
+        let oldest = (self.num_pushed + 1).saturating_sub(self.window);
+        self.minima
+            .iter()
+            .find(|&&(i, _)| i >= oldest)
+            .map_or(value, |&(_, min)| min.min(value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.num_pushed.min(self.window)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_pushed == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.num_pushed >= self.window
+    }
+
+    // Values still to be pushed before the window is full.
+    pub fn remaining(&self) -> usize {
+        self.window.saturating_sub(self.num_pushed)
+    }
+
+    pub fn clear(&mut self) {
+        self.num_pushed = 0;
+        self.maxima.clear();
+        self.minima.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn follows_the_window() {
+        let mut extrema = RollingExtrema::new(3);
+        assert!(extrema.is_empty());
+        assert_eq!((extrema.max(), extrema.min()), (None, None));
+        let expected = [
+            (3.0, 3.0, 3.0, 0, 0),
+            (1.0, 3.0, 1.0, 1, 0),
+            (2.0, 3.0, 1.0, 2, 1),
+            (5.0, 5.0, 1.0, 0, 2),
+            (4.0, 5.0, 2.0, 1, 2),
+            (4.0, 5.0, 4.0, 2, 0),
+        ];
+        for (value, max, min, max_age, min_age) in expected {
+            extrema.push(value);
+            assert_eq!((extrema.max(), extrema.min()), (Some(max), Some(min)));
+            assert_eq!(
+                (extrema.max_age(), extrema.min_age()),
+                (Some(max_age), Some(min_age))
+            );
+        }
+        assert!(extrema.is_full());
+        assert_eq!(extrema.len(), 3);
+    }
+
+    #[test]
+    fn looks_ahead_without_pushing() {
+        let mut extrema = RollingExtrema::new(2);
+        assert_eq!(extrema.max_with(1.0), 1.0);
+        extrema.push(9.0);
+        extrema.push(2.0);
+        // 9 would leave the window
+        assert_eq!(extrema.max_with(1.0), 2.0);
+        assert_eq!(extrema.min_with(1.0), 1.0);
+        assert_eq!(extrema.max(), Some(9.0));
+
+        extrema.clear();
+        assert_eq!(extrema.remaining(), 2);
+        assert_eq!(extrema.max(), None);
+    }
+
+    #[test]
+    fn matches_a_brute_force_scan() {
+        // A fixed linear congruential generator, values 0 to 19 so ties are common
+        let mut state: u64 = 42;
+        let values: Vec<f64> = (0..2_000)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                ((state >> 33) % 20) as f64
+            })
+            .collect();
+
+        for window in [1, 2, 5, 17] {
+            let mut extrema = RollingExtrema::new(window);
+            for (i, &value) in values.iter().enumerate() {
+                let scanned = &values[(i + 1).saturating_sub(window)..=i];
+                let (max, min) = extrema_of(scanned);
+                assert_eq!(
+                    (extrema.max_with(value), extrema.min_with(value)),
+                    (max, min)
+                );
+
+                extrema.push(value);
+                assert_eq!((extrema.max(), extrema.min()), (Some(max), Some(min)));
+                let age = |extreme: f64| scanned.iter().rev().position(|&v| v == extreme);
+                assert_eq!((extrema.max_age(), extrema.min_age()), (age(max), age(min)));
+            }
+        }
+    }
+
+    fn extrema_of(values: &[f64]) -> (f64, f64) {
+        values
+            .iter()
+            .fold((f64::MIN, f64::MAX), |(max, min), &value| {
+                (max.max(value), min.min(value))
+            })
+    }
+}
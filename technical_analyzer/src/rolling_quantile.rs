@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// The `q` quantile of the last `period` values, e.g. the median for 0.5, interpolated
+// linearly between the two nearest values like a spreadsheet's PERCENTILE. As an
+// `Indicator` it takes the closes. Handy as an adaptive threshold, e.g. the 0.9 quantile
+// of the volume for spotting unusually heavy bars.
+pub struct RollingQuantile {
+    period: usize,
+    // Between 0 and 1, clamped on construction
+    q: f64,
+    window: Window<f64>,
+    // The values of the window in ascending order, kept in step with it so a query only
+    // has to index into it
+    sorted: Vec<f64>,
+}
+
+impl RollingQuantile {
+    // Panics if `period` is 0, which has no values to rank.
+    pub fn new(period: usize, q: f64) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            q: q.clamp(0.0, 1.0),
+            window: Window::new(period),
+            sorted: Vec::with_capacity(period),
+        }
+    }
+
+    // Returns None until the window is full.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if !value.is_finite() {
+            return None;
+        }
+        if let Some(evicted) = self.window.push(value) {
+            if let Ok(index) = self.sorted.binary_search_by(|v| v.total_cmp(&evicted)) {
+                self.sorted.remove(index);
+            }
+        }
+        let index = self
+            .sorted
+            .binary_search_by(|v| v.total_cmp(&value))
+            .unwrap_or_else(|index| index);
+        self.sorted.insert(index, value);
+
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let rank = self.q * (self.period - 1) as f64;
+        let below = rank as usize;
+        let lower = self.sorted[below];
+        let quantile = match self.sorted.get(below + 1) {
+            Some(upper) => lower + (rank - below as f64) * (upper - lower),
+            None => lower,
+        };
+        Some(round_to(quantile, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sorted.clear();
+    }
+}
+
+impl Indicator for RollingQuantile {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        RollingQuantile::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        RollingQuantile::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_between_the_nearest_values() {
+        let mut median = RollingQuantile::new(4, 0.5);
+        for value in [4.0, 1.0, 3.0] {
+            assert_eq!(median.update(value), None);
+        }
+        assert_eq!(median.update(2.0), Some(2.5));
+        // 4 leaves the window
+        assert_eq!(median.update(10.0), Some(2.5));
+        assert_eq!(median.update(10.0), Some(6.5));
+
+        let mut quantile = RollingQuantile::new(5, 0.9);
+        for value in 1..5 {
+            quantile.update(value as f64);
+        }
+        assert_eq!(quantile.update(5.0), Some(4.6));
+    }
+
+    #[test]
+    fn extremes_and_clamping() {
+        let mut max = RollingQuantile::new(3, 1.5);
+        let mut min = RollingQuantile::new(3, 0.0);
+        for value in [2.0, 7.0, 5.0] {
+            max.update(value);
+            min.update(value);
+        }
+        assert_eq!(max.update(1.0), Some(7.0));
+        assert_eq!(min.update(1.0), Some(1.0));
+
+        max.reset();
+        assert_eq!(max.bars_until_ready(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        RollingQuantile::new(0, 0.5);
+    }
+}
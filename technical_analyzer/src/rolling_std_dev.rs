@@ -0,0 +1,189 @@
+use crate::utils::sqrt;
+use crate::window::Window;
+
+// Mean and variance of the last `period` values, updated in O(1) per value with Welford's
+// algorithm: a value leaving the window is replaced by the new one instead of summing the
+// squared deviations again. Rounding errors add up over a long stream, so both are
+// recomputed from the window once every `period` replacements, which stays amortized O(1).
+pub struct RollingStdDev {
+    period: usize,
+    window: Window<f64>,
+    mean: f64,
+    // Sum of squared deviations from the mean over the window
+    m2: f64,
+    num_replaced: usize,
+}
+
+impl RollingStdDev {
+    // Panics if `period` is 0, which has no values to deviate.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            window: Window::new(period),
+            mean: 0.0,
+            m2: 0.0,
+            num_replaced: 0,
+        }
+    }
+
+    // Add a value to the window and return the population standard deviation over the last
+    // `period` values, or None while fewer than `period` values have been seen.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if !value.is_finite() {
+            return None;
+        }
+        if let Some(evicted) = self.window.push(value) {
+            let n = self.period as f64;
+            let previous_mean = self.mean;
+            self.mean += (value - evicted) / n;
+            self.m2 += (value - evicted) * (value - self.mean + evicted - previous_mean);
+            self.num_replaced += 1;
+        } else {
+            let n = self.window.len() as f64;
+            let delta = value - self.mean;
+            self.mean += delta / n;
+            self.m2 += delta * (value - self.mean);
+        }
+
+        if self.num_replaced >= self.period {
+            self.resync();
+        }
+        // Rounding can leave a tiny negative sum when the window is constant
+        self.m2 = self.m2.max(0.0);
+
+        self.population_std_dev()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        self.is_full().then_some(self.mean)
+    }
+
+    // Variance dividing by `period`, for a window that is the whole population.
+    pub fn population_variance(&self) -> Option<f64> {
+        self.is_full().then(|| self.m2 / self.period as f64)
+    }
+
+    // Variance dividing by `period - 1`, for a window sampled from a larger series.
+    // None for a period below 2.
+    pub fn sample_variance(&self) -> Option<f64> {
+        (self.is_full() && self.period > 1).then(|| self.m2 / (self.period - 1) as f64)
+    }
+
+    pub fn population_std_dev(&self) -> Option<f64> {
+        self.population_variance().map(sqrt)
+    }
+
+    pub fn sample_std_dev(&self) -> Option<f64> {
+        self.sample_variance().map(sqrt)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    // Values still to be added before the window is full.
+    pub fn remaining(&self) -> usize {
+        self.window.remaining()
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.num_replaced = 0;
+    }
+
+    fn resync(&mut self) {
+        let n = self.window.len() as f64;
+        self.mean = self.window.iter().sum::<f64>() / n;
+        self.m2 = self
+            .window
+            .iter()
+            .map(|value| (value - self.mean) * (value - self.mean))
+            .sum();
+        self.num_replaced = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn population_and_sample_deviation() {
+        let mut std_dev = RollingStdDev::new(8);
+        let mut last = None;
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            last = std_dev.update(value);
+        }
+        assert_eq!(last, Some(2.0));
+        assert_eq!(std_dev.mean(), Some(5.0));
+        assert_eq!(std_dev.sample_variance(), Some(32.0 / 7.0));
+
+        let mut std_dev = RollingStdDev::new(2);
+        assert_eq!(std_dev.update(1.0), None);
+        assert_eq!(std_dev.remaining(), 1);
+        assert_eq!(std_dev.update(3.0), Some(1.0));
+        assert_eq!(std_dev.update(7.0), Some(2.0));
+        assert_eq!(std_dev.sample_variance(), Some(8.0));
+        assert_eq!(std_dev.update(f64::NAN), None);
+
+        std_dev.reset();
+        assert_eq!(std_dev.mean(), None);
+    }
+
+    #[test]
+    fn stays_accurate_over_a_long_stream() {
+        let mut std_dev = RollingStdDev::new(3);
+        for i in 0..10_000 {
+            std_dev.update(1e6 + (i % 7) as f64);
+        }
+        // The window holds 1e6 + 1, 2 and 3
+        assert_eq!(std_dev.mean(), Some(1e6 + 2.0));
+        assert!((std_dev.population_variance().unwrap() - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(RollingStdDev::new(1).sample_variance(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        RollingStdDev::new(0);
+    }
+
+    #[test]
+    fn matches_a_two_pass_variance_of_a_random_series() {
+        // A fixed linear congruential generator, values between 50 and 150
+        let mut state: u64 = 7;
+        let values: Vec<f64> = (0..5_000)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                50.0 + (state >> 11) as f64 / (1u64 << 53) as f64 * 100.0
+            })
+            .collect();
+
+        for period in [1, 2, 7, 30] {
+            let mut std_dev = RollingStdDev::new(period);
+            for (i, &value) in values.iter().enumerate() {
+                let result = std_dev.update(value);
+                if i + 1 < period {
+                    assert_eq!(result, None);
+                    continue;
+                }
+                let window = &values[i + 1 - period..=i];
+                let mean = window.iter().sum::<f64>() / period as f64;
+                let variance = window
+                    .iter()
+                    .map(|value| (value - mean) * (value - mean))
+                    .sum::<f64>()
+                    / period as f64;
+                assert!((std_dev.mean().unwrap() - mean).abs() < 1e-9);
+                assert!((std_dev.population_variance().unwrap() - variance).abs() < 1e-9);
+                assert!((result.unwrap() - sqrt(variance)).abs() < 1e-9);
+            }
+        }
+    }
+}
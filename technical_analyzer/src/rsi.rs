@@ -0,0 +1,122 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+
+pub struct RelativeStrengthIndex {
+    period: usize,
+    previous_close: Option<f64>,
+    // Gains and losses are summed over the first `period` changes to seed the averages,
+    // after which Wilder's smoothing takes over.
+    num_changes: usize,
+    average_gain: f64,
+    average_loss: f64,
+}
+
+impl RelativeStrengthIndex {
+    // Panics if `period` is 0, which has no average.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            previous_close: None,
+            num_changes: 0,
+            average_gain: 0.0,
+            average_loss: 0.0,
+        }
+    }
+
+    // Returns None until `period + 1` closes have been seen.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        let previous_close = self.previous_close.replace(close)?;
+        let change = close - previous_close;
+        let gain = change.max(0.0);
+        let loss = (-change).max(0.0);
+
+        self.num_changes += 1;
+        let period = self.period as f64;
+        if self.num_changes <= self.period {
+            self.average_gain += gain / period;
+            self.average_loss += loss / period;
+            if self.num_changes < self.period {
+                return None;
+            }
+        } else {
+            self.average_gain = (self.average_gain * (period - 1.0) + gain) / period;
+            self.average_loss = (self.average_loss * (period - 1.0) + loss) / period;
+        }
+
+        let rsi = if self.average_loss == 0.0 {
+            // No losses at all means maximum strength, unless the price didn't move either.
+            if self.average_gain == 0.0 {
+                50.0
+            } else {
+                100.0
+            }
+        } else {
+            100.0 - 100.0 / (1.0 + self.average_gain / self.average_loss)
+        };
+        Some(round_to(rsi, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.previous_close = None;
+        self.num_changes = 0;
+        self.average_gain = 0.0;
+        self.average_loss = 0.0;
+    }
+}
+
+impl Indicator for RelativeStrengthIndex {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        RelativeStrengthIndex::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        RelativeStrengthIndex::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        usize::from(self.previous_close.is_none()) + self.period.saturating_sub(self.num_changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_then_smooths_the_averages() {
+        let mut rsi = RelativeStrengthIndex::new(2);
+        assert_eq!(rsi.update(10.0), None);
+        assert_eq!(rsi.update(11.0), None);
+        // Gains 1 and 0, losses 0 and 0.5 over the first two changes
+        assert_eq!(rsi.update(10.5), Some(66.66666667));
+        // Wilder's smoothing: gain (0.5 + 1.5) / 2, loss (0.25 + 0) / 2
+        assert_eq!(rsi.update(12.0), Some(88.88888889));
+    }
+
+    #[test]
+    fn flat_and_rising_series() {
+        let mut flat = RelativeStrengthIndex::new(3);
+        let mut rising = RelativeStrengthIndex::new(3);
+        for close in 0..5 {
+            flat.update(5.0);
+            rising.update(close as f64);
+        }
+        assert_eq!(flat.update(5.0), Some(50.0));
+        assert_eq!(rising.update(5.0), Some(100.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        RelativeStrengthIndex::new(0);
+    }
+}
@@ -0,0 +1,53 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+
+pub(crate) const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// Indices of the candles that open a new UTC calendar day, i.e. whose timestamp falls on
+// a later (or earlier) day than the last timestamped candle before them. The first candle
+// is never a boundary. Candles without a timestamp belong to the current session.
+pub fn session_boundaries(candles: &[Candlestick]) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut current_day = None;
+
+    for (index, candle) in candles.iter().enumerate() {
+        let Some(timestamp) = candle.timestamp else {
+            continue;
+        };
+        let day = timestamp.div_euclid(SECONDS_PER_DAY);
+        if current_day.is_some_and(|current| current != day) {
+            boundaries.push(index);
+        }
+        current_day = Some(day);
+    }
+
+    boundaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: Option<i64>) -> Candlestick {
+        Candlestick {
+            timestamp,
+            ..Candlestick::ohlc(1.0, 1.0, 1.0, 1.0)
+        }
+    }
+
+    #[test]
+    fn a_new_day_opens_a_session() {
+        let candles = [
+            at(Some(10)),
+            at(Some(20)),
+            at(None),
+            at(Some(SECONDS_PER_DAY + 5)),
+            at(Some(SECONDS_PER_DAY + 6)),
+            at(Some(30)),
+            at(Some(-1)),
+        ];
+        assert_eq!(session_boundaries(&candles), [3, 5, 6]);
+        assert!(session_boundaries(&candles[..3]).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+use crate::window::Window;
+
+pub struct SimpleMovingAverage {
+    period: usize,
+    // The last `period` closes and their running sum.
+    window: Window<f64>,
+    sum: f64,
+}
+
+impl SimpleMovingAverage {
+    // Panics if `period` is 0, which has no average.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            window: Window::new(period),
+            sum: 0.0,
+        }
+    }
+
+    // Add a close to the window and return the average over the last `period` closes,
+    // or None while fewer than `period` closes have been seen.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        self.sum += close;
+        if let Some(evicted) = self.window.push(close) {
+            self.sum -= evicted;
+        }
+
+        if !self.window.is_full() {
+            return None;
+        }
+        Some(round_to(self.sum / self.period as f64, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+    }
+}
+
+impl Indicator for SimpleMovingAverage {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        SimpleMovingAverage::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        SimpleMovingAverage::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn constant_series_averages_to_the_constant() {
+        let mut sma = SimpleMovingAverage::new(4);
+        let averages: Vec<_> = (0..10).map(|_| sma.update(7.25)).collect();
+        assert_eq!(averages[..3], [None, None, None]);
+        assert!(averages[3..].iter().all(|&average| average == Some(7.25)));
+    }
+
+    #[test]
+    fn ramp_averages_the_last_period_closes() {
+        let mut sma = SimpleMovingAverage::new(3);
+        let averages: Vec<_> = (1..=6).map(|close| sma.update(close as f64)).collect();
+        assert_eq!(
+            averages,
+            [None, None, Some(2.0), Some(3.0), Some(4.0), Some(5.0)]
+        );
+        sma.reset();
+        assert_eq!(sma.update(1.0), None);
+    }
+
+    #[test]
+    fn skips_non_finite_closes() {
+        let mut sma = SimpleMovingAverage::new(2);
+        assert_eq!(sma.update(1.0), None);
+        assert_eq!(sma.update(f64::NAN), None);
+        assert_eq!(sma.update(3.0), Some(2.0));
+        assert_eq!(sma.update(f64::NEG_INFINITY), None);
+        assert_eq!(sma.update(5.0), Some(4.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        SimpleMovingAverage::new(0);
+    }
+}
@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use crate::bollinger::BollingerBands;
+use crate::candlestick::Candlestick;
+use crate::donchian::DonchianChannels;
+use crate::indicator::{chained, Indicator};
+use crate::keltner::KeltnerChannels;
+use crate::regression::linear_regression;
+use crate::utils::round_to;
+use crate::window::Window;
+
+pub struct SqueezeState {
+    // The Bollinger Bands are entirely inside the Keltner Channels
+    pub squeeze_on: bool,
+    // Positive when the close is above its recent range and rising, negative below it
+    pub momentum: f64,
+}
+
+// Volatility squeeze in the style of the TTM Squeeze. While Bollinger Bands fit inside
+// the Keltner Channels volatility is unusually low, and the move after the squeeze
+// releases tends to follow the sign of the momentum.
+pub struct SqueezeDetector {
+    bollinger: BollingerBands,
+    keltner: KeltnerChannels,
+    donchian: DonchianChannels,
+    // Distance of the close from the middle of its range, see `update`
+    deltas: Window<f64>,
+}
+
+impl SqueezeDetector {
+    // Bollinger Bands of `period` closes `bollinger_mult` standard deviations wide,
+    // Keltner Channels with an EMA and ATR of `period` and `keltner_mult` ATRs wide.
+    pub fn new(period: usize, bollinger_mult: f64, keltner_mult: f64) -> Self {
+        Self {
+            bollinger: BollingerBands::new(period, bollinger_mult),
+            keltner: KeltnerChannels::new(period, period, keltner_mult),
+            donchian: DonchianChannels::new(period),
+            deltas: Window::new(period),
+        }
+    }
+
+    // The momentum is the close minus the average of the Donchian and Bollinger middle
+    // lines, smoothed by the end of a linear regression over the last `period` of them.
+    // Returns None until that regression has a full window, after `2 * period - 1` candles.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<SqueezeState> {
+        let bollinger = self.bollinger.update(candle.close);
+        let keltner = self.keltner.update(candle);
+        let donchian = self.donchian.update(candle);
+        let (Some(bollinger), Some(keltner), Some(donchian)) = (bollinger, keltner, donchian)
+        else {
+            return None;
+        };
+
+        self.deltas
+            .push(candle.close - (donchian.middle + bollinger.middle) / 2.0);
+        if !self.deltas.is_full() {
+            return None;
+        }
+        let deltas: Vec<f64> = self.deltas.iter().copied().collect();
+        let (slope, intercept) = linear_regression(&deltas);
+
+        Some(SqueezeState {
+            squeeze_on: bollinger.upper < keltner.upper && bollinger.lower > keltner.lower,
+            momentum: round_to(intercept + slope * (deltas.len() - 1) as f64, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.bollinger.reset();
+        self.keltner.reset();
+        self.donchian.reset();
+        self.deltas.clear();
+    }
+}
+
+// The usual 20 period configuration with 2 standard deviation Bollinger Bands and 1.5
+// ATR Keltner Channels.
+impl Default for SqueezeDetector {
+    fn default() -> Self {
+        Self::new(20, 2.0, 1.5)
+    }
+}
+
+impl Indicator for SqueezeDetector {
+    type Output = SqueezeState;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<SqueezeState> {
+        SqueezeDetector::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        SqueezeDetector::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        let bands = self
+            .bollinger
+            .bars_until_ready()
+            .max(self.keltner.bars_until_ready())
+            .max(self.donchian.bars_until_ready());
+        chained(bands, self.deltas.remaining())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_closes_squeeze_inside_the_channels() {
+        let mut squeeze = SqueezeDetector::new(2, 2.0, 1.5);
+        assert_eq!(squeeze.bars_until_ready(), 3);
+        let candle = Candlestick::ohlc(10.0, 11.0, 9.0, 10.0);
+        assert!(squeeze.update(&candle).is_none());
+        assert!(squeeze.update(&candle).is_none());
+        // The closes don't deviate while the candles still have a range
+        let state = squeeze.update(&candle).unwrap();
+        assert!(state.squeeze_on);
+        assert_eq!(state.momentum, 0.0);
+
+        squeeze.reset();
+        assert_eq!(squeeze.bars_until_ready(), 3);
+    }
+
+    #[test]
+    fn a_breakout_releases_the_squeeze() {
+        let mut squeeze = SqueezeDetector::new(2, 2.0, 1.5);
+        for _ in 0..3 {
+            squeeze.update(&Candlestick::ohlc(10.0, 10.5, 9.5, 10.0));
+        }
+        let state = squeeze
+            .update(&Candlestick::ohlc(10.0, 20.0, 10.0, 20.0))
+            .unwrap();
+        assert!(!state.squeeze_on);
+        assert!(state.momentum > 0.0);
+    }
+}
@@ -0,0 +1,124 @@
+use alloc::vec::Vec;
+
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::regression::linear_regression;
+use crate::units::Price;
+use crate::utils::{round_to, sqrt};
+use crate::window::Window;
+
+pub struct StandardErrorResult {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+// Bands around the end of a regression line fitted through the last `period` closes,
+// `multiplier` standard errors of estimate above and below it. They tighten while the
+// closes follow a straight line and widen when they scatter around it.
+pub struct StandardErrorBands {
+    multiplier: f64,
+    window: Window<f64>,
+}
+
+impl StandardErrorBands {
+    // Panics if `period` is 0, which has no line to fit.
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            multiplier,
+            window: Window::new(period),
+        }
+    }
+
+    // The middle band is the regression line at the latest close. The standard error of
+    // estimate is the root of the squared residuals summed and divided by `period - 2`,
+    // so it's 0 for two closes or fewer, which a line always fits. Returns None until the
+    // window is full.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<StandardErrorResult> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        self.window.push(close);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let closes: Vec<f64> = self.window.iter().copied().collect();
+        let (slope, intercept) = linear_regression(&closes);
+        let squared_residuals: f64 = closes
+            .iter()
+            .enumerate()
+            .map(|(x, close)| {
+                let residual = close - (intercept + slope * x as f64);
+                residual * residual
+            })
+            .sum();
+        let degrees_of_freedom = closes.len().saturating_sub(2);
+        let standard_error = if degrees_of_freedom == 0 {
+            0.0
+        } else {
+            sqrt(squared_residuals / degrees_of_freedom as f64)
+        };
+
+        let middle = intercept + slope * (closes.len() - 1) as f64;
+        let offset = self.multiplier * standard_error;
+        Some(StandardErrorResult {
+            upper: round_to(middle + offset, 8),
+            middle: round_to(middle, 8),
+            lower: round_to(middle - offset, 8),
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Indicator for StandardErrorBands {
+    type Output = StandardErrorResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<StandardErrorResult> {
+        StandardErrorBands::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        StandardErrorBands::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_widen_with_the_scatter_around_the_line() {
+        let mut bands = StandardErrorBands::new(3, 2.0);
+        assert!(bands.update(1.0).is_none());
+        assert!(bands.update(2.0).is_none());
+        // A straight line has no error
+        let result = bands.update(3.0).unwrap();
+        assert_eq!((result.upper, result.middle, result.lower), (3.0, 3.0, 3.0));
+
+        // 2, 3 and 5 fit 11/6 + 1.5x with an error of the root of 1/6
+        let result = bands.update(5.0).unwrap();
+        assert_eq!(
+            (result.upper, result.middle, result.lower),
+            (5.64982991, 4.83333333, 4.01683675)
+        );
+
+        bands.reset();
+        assert_eq!(bands.bars_until_ready(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        StandardErrorBands::new(0, 2.0);
+    }
+}
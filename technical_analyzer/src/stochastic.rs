@@ -0,0 +1,112 @@
+use crate::candlestick::Candlestick;
+use crate::high_low::HighLowWindow;
+use crate::indicator::{chained, Indicator};
+use crate::sma::SimpleMovingAverage;
+use crate::utils::round_to;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StochResult {
+    pub k: f64,
+    pub d: f64,
+}
+
+pub struct StochasticOscillator {
+    window: HighLowWindow,
+    // %D is the moving average of %K
+    d: SimpleMovingAverage,
+}
+
+impl StochasticOscillator {
+    // Panics if either period is 0.
+    pub fn new(k_period: usize, d_period: usize) -> Self {
+        assert!(k_period > 0, "period must be at least 1");
+        Self {
+            window: HighLowWindow::new(k_period),
+            d: SimpleMovingAverage::new(d_period),
+        }
+    }
+
+    // Returns None until both %K and %D are available, after `k_period + d_period - 1`
+    // candles. A window without any range puts %K in the middle, at 50.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<StochResult> {
+        self.window.push(candle.high, candle.low);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let highest = self.window.highest();
+        let lowest = self.window.lowest();
+        let k = if highest == lowest {
+            50.0
+        } else {
+            100.0 * (candle.close - lowest) / (highest - lowest)
+        };
+        let k = round_to(k, 8);
+        let d = self.d.update(k)?;
+
+        Some(StochResult { k, d })
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+        self.d.reset();
+    }
+}
+
+impl Indicator for StochasticOscillator {
+    type Output = StochResult;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<StochResult> {
+        StochasticOscillator::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        StochasticOscillator::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        chained(self.window.remaining(), self.d.bars_until_ready())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_places_the_close_in_the_range_and_d_averages_it() {
+        let mut stochastic = StochasticOscillator::new(3, 2);
+        assert!(stochastic
+            .update(&Candlestick::ohlc(1.0, 2.0, 1.0, 1.0))
+            .is_none());
+        assert!(stochastic
+            .update(&Candlestick::ohlc(1.0, 3.0, 1.0, 2.0))
+            .is_none());
+        // %K is 100 with the close at the highest high, but %D needs two of them
+        assert!(stochastic
+            .update(&Candlestick::ohlc(2.0, 4.0, 2.0, 4.0))
+            .is_none());
+        assert_eq!(stochastic.bars_until_ready(), 1);
+        let result = stochastic
+            .update(&Candlestick::ohlc(2.0, 4.0, 2.0, 2.0))
+            .unwrap();
+        assert_eq!(result.k, 33.33333333);
+        assert_eq!(result.d, 66.66666667);
+    }
+
+    #[test]
+    fn flat_range_is_the_middle() {
+        let mut stochastic = StochasticOscillator::new(2, 1);
+        stochastic.update(&Candlestick::ohlc(1.0, 1.0, 1.0, 1.0));
+        let result = stochastic
+            .update(&Candlestick::ohlc(1.0, 1.0, 1.0, 1.0))
+            .unwrap();
+        assert_eq!((result.k, result.d), (50.0, 50.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        StochasticOscillator::new(0, 3);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::candlestick::Candlestick;
+use crate::renko::Direction;
+use crate::units::Price;
+use crate::utils::round_to;
+
+// Where a trailing stop on a position opened at `entry` would have been hit, as the index
+// of the candle and the fill price. `trail_pct` is the distance of the stop from the best
+// price seen so far as a fraction, e.g. 0.05 for 5%. An Up position is long, so its stop
+// sits below the highest high and only moves up; a Down position is short and its stop
+// sits above the lowest low and only moves down.
+//
+// The order of prices within a candle is unknown, so each candle is checked against the
+// stop as it stood before that candle, and only then can move the stop. A candle opening
+// beyond the stop fills at its open. Returns None if the stop never triggers.
+pub fn trailing_stop(
+    entry: impl Into<Price>,
+    candles: &[Candlestick],
+    trail_pct: f64,
+    direction: Direction,
+) -> Option<(usize, f64)> {
+    let mut best = entry.into().value();
+    for (index, candle) in candles.iter().enumerate() {
+        match direction {
+            Direction::Up => {
+                let stop = best * (1.0 - trail_pct);
+                if candle.low <= stop {
+                    return Some((index, round_to(candle.open.min(stop), 8)));
+                }
+                best = best.max(candle.high);
+            }
+            Direction::Down => {
+                let stop = best * (1.0 + trail_pct);
+                if candle.high >= stop {
+                    return Some((index, round_to(candle.open.max(stop), 8)));
+                }
+                best = best.min(candle.low);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_stop_trails_the_highest_high() {
+        let candles = [
+            Candlestick::ohlc(100.0, 110.0, 95.0, 108.0),
+            Candlestick::ohlc(108.0, 109.0, 100.0, 101.0),
+            Candlestick::ohlc(101.0, 102.0, 98.0, 99.0),
+        ];
+        // The stop moves from 90 to 99 after the first candle
+        assert_eq!(
+            trailing_stop(100.0, &candles, 0.1, Direction::Up),
+            Some((2, 99.0))
+        );
+        assert_eq!(
+            trailing_stop(100.0, &candles[..2], 0.1, Direction::Up),
+            None
+        );
+
+        // Opening below the stop fills at the open
+        let gap = [
+            candles[0].clone(),
+            Candlestick::ohlc(97.0, 98.0, 96.0, 97.0),
+        ];
+        assert_eq!(
+            trailing_stop(100.0, &gap, 0.1, Direction::Up),
+            Some((1, 97.0))
+        );
+    }
+
+    #[test]
+    fn short_stop_trails_the_lowest_low() {
+        let candles = [
+            Candlestick::ohlc(100.0, 105.0, 90.0, 92.0),
+            Candlestick::ohlc(92.0, 100.0, 91.0, 99.0),
+        ];
+        assert_eq!(
+            trailing_stop(100.0, &candles, 0.1, Direction::Down),
+            Some((1, 99.0))
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::candlestick::Candlestick;
+use crate::ema::ExponentialMovingAverage;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+
+// Percentage change from one bar to the next of a triple smoothed EMA of the closes.
+pub struct Trix {
+    first: ExponentialMovingAverage,
+    second: ExponentialMovingAverage,
+    third: ExponentialMovingAverage,
+    period: usize,
+    num_processed: usize,
+    previous: Option<f64>,
+}
+
+impl Trix {
+    pub fn new(period: usize) -> Self {
+        Self {
+            first: ExponentialMovingAverage::new_seeded(period),
+            second: ExponentialMovingAverage::new_seeded(period),
+            third: ExponentialMovingAverage::new_seeded(period),
+            period,
+            num_processed: 0,
+            previous: None,
+        }
+    }
+
+    // Each average only feeds the next one once it has seen `period` values, so the
+    // triple average is ready after `3 * period - 2` closes. Returns None until one more
+    // close gives it something to compare against, or when the previous value was zero.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        self.num_processed += 1;
+        let first = self.first.update(close)?;
+        if self.num_processed < self.period {
+            return None;
+        }
+        let second = self.second.update(first)?;
+        if self.num_processed + 1 < 2 * self.period {
+            return None;
+        }
+        let third = self.third.update(second)?;
+        if self.num_processed + 2 < 3 * self.period {
+            return None;
+        }
+
+        let previous = self.previous.replace(third)?;
+        if previous == 0.0 {
+            return None;
+        }
+        Some(round_to(100.0 * (third - previous) / previous, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.third.reset();
+        self.num_processed = 0;
+        self.previous = None;
+    }
+}
+
+impl Indicator for Trix {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        Trix::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        Trix::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        // Each EMA starts on the candle the one before it is seeded, and the rate of
+        // change needs one more value of the last one
+        (3 * self.period).saturating_sub(1 + self.num_processed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_of_change_of_the_triple_average() {
+        // With a period of 1 every average is the close itself
+        let mut trix = Trix::new(1);
+        assert_eq!(Indicator::bars_until_ready(&trix), 2);
+        assert_eq!(trix.update(10.0), None);
+        assert_eq!(trix.update(11.0), Some(10.0));
+        assert_eq!(trix.update(8.8), Some(-20.0));
+    }
+
+    #[test]
+    fn ready_after_three_seeded_averages() {
+        let mut trix = Trix::new(2);
+        assert_eq!(Indicator::bars_until_ready(&trix), 5);
+        for _ in 0..4 {
+            assert_eq!(trix.update(5.0), None);
+        }
+        assert_eq!(Indicator::bars_until_ready(&trix), 1);
+        assert_eq!(trix.update(5.0), Some(0.0));
+
+        trix.reset();
+        assert_eq!(Indicator::bars_until_ready(&trix), 5);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::atr::true_range;
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// Buying pressure and true range summed over the last `period` candles.
+struct PressureWindow {
+    window: Window<(f64, f64)>,
+    buying_pressure: f64,
+    true_range: f64,
+}
+
+impl PressureWindow {
+    fn new(period: usize) -> Self {
+        Self {
+            window: Window::new(period),
+            buying_pressure: 0.0,
+            true_range: 0.0,
+        }
+    }
+
+    fn push(&mut self, buying_pressure: f64, true_range: f64) {
+        self.buying_pressure += buying_pressure;
+        self.true_range += true_range;
+        if let Some((evicted_pressure, evicted_range)) =
+            self.window.push((buying_pressure, true_range))
+        {
+            self.buying_pressure -= evicted_pressure;
+            self.true_range -= evicted_range;
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    // Buying pressure as a share of the true range, 0.5 when the window has no range.
+    fn average(&self) -> f64 {
+        if self.true_range <= 0.0 {
+            0.5
+        } else {
+            self.buying_pressure / self.true_range
+        }
+    }
+
+    fn clear(&mut self) {
+        self.window.clear();
+        self.buying_pressure = 0.0;
+        self.true_range = 0.0;
+    }
+}
+
+// Weighted average of buying pressure over true range across a short, medium and long
+// window, weighted 4, 2 and 1, scaled to 0..100.
+pub struct UltimateOscillator {
+    short: PressureWindow,
+    medium: PressureWindow,
+    long: PressureWindow,
+    previous_close: Option<f64>,
+}
+
+impl UltimateOscillator {
+    // Panics if any period is 0.
+    pub fn new(short: usize, medium: usize, long: usize) -> Self {
+        assert!(
+            short > 0 && medium > 0 && long > 0,
+            "period must be at least 1"
+        );
+        Self {
+            short: PressureWindow::new(short),
+            medium: PressureWindow::new(medium),
+            long: PressureWindow::new(long),
+            previous_close: None,
+        }
+    }
+
+    // Buying pressure is the close minus the lower of the low and the previous close, so
+    // the first candle only provides a previous close. Returns None until all three
+    // windows are full, after `long + 1` candles when `long` is the longest period.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        let previous_close = self.previous_close.replace(candle.close)?;
+        let buying_pressure = candle.close - candle.low.min(previous_close);
+        let true_range = true_range(candle, Some(previous_close));
+
+        for window in [&mut self.short, &mut self.medium, &mut self.long] {
+            window.push(buying_pressure, true_range);
+        }
+        if !(self.short.is_full() && self.medium.is_full() && self.long.is_full()) {
+            return None;
+        }
+
+        let weighted =
+            4.0 * self.short.average() + 2.0 * self.medium.average() + self.long.average();
+        Some(round_to(100.0 * weighted / 7.0, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.short.clear();
+        self.medium.clear();
+        self.long.clear();
+        self.previous_close = None;
+    }
+}
+
+impl Default for UltimateOscillator {
+    // The usual 7/14/28 configuration.
+    fn default() -> Self {
+        Self::new(7, 14, 28)
+    }
+}
+
+impl Indicator for UltimateOscillator {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        UltimateOscillator::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        UltimateOscillator::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        let windows = self
+            .short
+            .window
+            .remaining()
+            .max(self.medium.window.remaining())
+            .max(self.long.window.remaining());
+        usize::from(self.previous_close.is_none()) + windows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighs_the_short_window_most() {
+        let mut ultimate = UltimateOscillator::new(1, 1, 2);
+        assert_eq!(ultimate.bars_until_ready(), 3);
+        assert_eq!(
+            ultimate.update(&Candlestick::ohlc(10.0, 10.0, 10.0, 10.0)),
+            None
+        );
+        // Buying pressure of 2 over a true range of 3
+        assert_eq!(
+            ultimate.update(&Candlestick::ohlc(10.0, 12.0, 9.0, 11.0)),
+            None
+        );
+        // Then 2 over 2, so the short and medium windows are at 1 and the long one at 0.8
+        assert_eq!(
+            ultimate.update(&Candlestick::ohlc(11.0, 12.0, 10.0, 12.0)),
+            Some(97.14285714)
+        );
+        // A candle without range is neutral on its own, the long window still has 2 over 2
+        assert_eq!(
+            ultimate.update(&Candlestick::ohlc(12.0, 12.0, 12.0, 12.0)),
+            Some(57.14285714)
+        );
+
+        ultimate.reset();
+        assert_eq!(ultimate.bars_until_ready(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        UltimateOscillator::new(7, 0, 28);
+    }
+}
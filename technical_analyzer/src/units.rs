@@ -0,0 +1,151 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+// A price, kept apart from volumes so the two can't be swapped by accident. Converts from
+// and into a bare f64, so `100.5.into()` works wherever a Price is expected. Candles take
+// and hand out their prices as these, and so do the indicators updated with a close.
+// Indicators of an arbitrary series, like `RollingZScore` or `RollingQuantile`, stay on f64.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Price(f64);
+
+// A traded quantity, see `Price`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Volume(f64);
+
+impl Price {
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl Volume {
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+// Prices and volumes add and subtract among their own kind, scale by plain numbers and
+// compare with plain numbers. The ratio of two of a kind is a plain number.
+macro_rules! quantity_ops {
+    ($unit:ident) => {
+        impl From<f64> for $unit {
+            fn from(value: f64) -> Self {
+                $unit(value)
+            }
+        }
+
+        impl From<$unit> for f64 {
+            fn from(value: $unit) -> f64 {
+                value.0
+            }
+        }
+
+        impl PartialEq<f64> for $unit {
+            fn eq(&self, other: &f64) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialOrd<f64> for $unit {
+            fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+                self.0.partial_cmp(other)
+            }
+        }
+
+        impl fmt::Display for $unit {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl Add for $unit {
+            type Output = $unit;
+
+            fn add(self, other: $unit) -> $unit {
+                $unit(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = $unit;
+
+            fn sub(self, other: $unit) -> $unit {
+                $unit(self.0 - other.0)
+            }
+        }
+
+        impl AddAssign for $unit {
+            fn add_assign(&mut self, other: $unit) {
+                self.0 += other.0;
+            }
+        }
+
+        impl SubAssign for $unit {
+            fn sub_assign(&mut self, other: $unit) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Mul<f64> for $unit {
+            type Output = $unit;
+
+            fn mul(self, factor: f64) -> $unit {
+                $unit(self.0 * factor)
+            }
+        }
+
+        impl Div<f64> for $unit {
+            type Output = $unit;
+
+            fn div(self, divisor: f64) -> $unit {
+                $unit(self.0 / divisor)
+            }
+        }
+
+        impl Div for $unit {
+            type Output = f64;
+
+            fn div(self, other: $unit) -> f64 {
+                self.0 / other.0
+            }
+        }
+    };
+}
+
+quantity_ops!(Price);
+quantity_ops!(Volume);
+
+// Price times volume is a traded value, in the quote currency.
+impl Mul<Volume> for Price {
+    type Output = f64;
+
+    fn mul(self, volume: Volume) -> f64 {
+        self.0 * volume.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_stays_within_a_unit() {
+        let price = Price::from(10.0) + 2.5.into();
+        assert_eq!(price, Price::from(12.5));
+        assert_eq!((price - Price::from(2.5)) * 2.0, Price::from(20.0));
+        assert_eq!(price / Price::from(2.5), 5.0);
+        assert_eq!(price / 5.0, 2.5);
+        assert!(price > 12.0);
+        assert_eq!(Price::from(2.0) * Volume::from(3.0), 6.0);
+
+        let mut volume = Volume::from(1.5);
+        volume += Volume::from(2.0);
+        volume -= Volume::from(0.5);
+        assert_eq!(f64::from(volume), 3.0);
+    }
+}
@@ -0,0 +1,100 @@
+// Float functions missing from `core`. With std they are the usual methods, without it
+// they come from libm so the indicators still build for no_std targets.
+
+// Round to `decimals` decimal places without going through a string. Results use 8
+// unless an indicator is configured otherwise. NaN, infinities and values too large to
+// scale are returned unchanged.
+pub(crate) fn round_to(value: f64, decimals: u32) -> f64 {
+    let factor = (0..decimals).fold(1.0, |factor, _| factor * 10.0);
+    let scaled = value * factor;
+    if !scaled.is_finite() {
+        return value;
+    }
+    round(scaled) / factor
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn round(value: f64) -> f64 {
+    value.round()
+}
+
+// Rounds half away from zero like `f64::round`.
+#[cfg(not(feature = "std"))]
+pub(crate) fn round(value: f64) -> f64 {
+    libm::round(value)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sqrt(value: f64) -> f64 {
+    value.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sqrt(value: f64) -> f64 {
+    libm::sqrt(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_half_away_from_zero() {
+        assert_eq!(round_to(1.234_567_895, 8), 1.234_567_9);
+        assert_eq!(round_to(-2.5, 0), -3.0);
+        assert_eq!(round_to(0.125, 2), 0.13);
+        assert!(round_to(f64::NAN, 8).is_nan());
+        assert_eq!(round_to(f64::MAX, 8), f64::MAX);
+    }
+
+    // What rounding used to be, formatting to 8 decimals and parsing the string back
+    fn formatted(value: f64) -> f64 {
+        alloc::format!("{:.8}", value).parse().unwrap()
+    }
+
+    #[test]
+    fn agrees_with_rounding_through_a_string() {
+        let values = [
+            0.0,
+            42.0,
+            1.0 / 3.0,
+            -2.0 / 3.0,
+            1.234_567_895,
+            -1.234_567_895,
+            100.000_000_005,
+            -98_765.432_109_876,
+            5e-9,
+            -5e-9,
+            1.5e-8,
+            1e-9,
+            -1e-9,
+            4e-9,
+            6e-9,
+            0.005_859_375,
+            1e12 + 0.123_456_785,
+            9_007_199_254_740_992.0,
+            1e300,
+        ];
+        for value in values {
+            assert_eq!(round_to(value, 8), formatted(value), "{}", value);
+        }
+
+        // Past 1e8 a 1e-8 step is finer than the spacing of f64, the two can be a unit in
+        // the last place apart
+        let large = 123_456_789.123_456_79;
+        assert!((round_to(large, 8) - formatted(large)).abs() <= large * f64::EPSILON);
+
+        // A decimal tie that is exact in binary, 1/512 = 0.001953125, now rounds away from
+        // zero where the string rounded to even
+        assert_eq!(formatted(1.0 / 512.0), 0.001_953_12);
+        assert_eq!(round_to(1.0 / 512.0, 8), 0.001_953_13);
+        assert_eq!(round_to(-1.0 / 512.0, 8), -0.001_953_13);
+    }
+
+    #[test]
+    fn square_roots() {
+        assert_eq!(sqrt(2.25), 1.5);
+        assert_eq!(sqrt(0.0), 0.0);
+        assert!(sqrt(-1.0).is_nan());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+// Volume-weighted average of the typical price, (high + low + close) / 3, since the last
+// reset. Call `reset` at every session boundary, see `session_boundaries` for daily
+// sessions.
+pub struct Vwap {
+    weighted_price_sum: f64,
+    volume_sum: f64,
+}
+
+impl Vwap {
+    pub fn new() -> Self {
+        Self {
+            weighted_price_sum: 0.0,
+            volume_sum: 0.0,
+        }
+    }
+
+    // Returns None for a candle without volume, which isn't added to the average, and
+    // while no volume has been traded in the session.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        let volume = candle.volume?;
+        let typical_price = (candle.high + candle.low + candle.close) / 3.0;
+        self.weighted_price_sum += typical_price * volume;
+        self.volume_sum += volume;
+
+        if self.volume_sum == 0.0 {
+            return None;
+        }
+        Some(round_to(self.weighted_price_sum / self.volume_sum, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.weighted_price_sum = 0.0;
+        self.volume_sum = 0.0;
+    }
+}
+
+impl Default for Vwap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Vwap {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        Vwap::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        Vwap::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        usize::from(self.volume_sum == 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64, low: f64, close: f64, volume: f64) -> Candlestick {
+        Candlestick {
+            volume: Some(volume),
+            ..Candlestick::ohlc(close, high, low, close)
+        }
+    }
+
+    #[test]
+    fn weighs_the_typical_price_by_volume() {
+        let mut vwap = Vwap::new();
+        assert_eq!(vwap.bars_until_ready(), 1);
+        // Typical prices of 11 and 20
+        assert_eq!(vwap.update(&candle(12.0, 9.0, 12.0, 100.0)), Some(11.0));
+        assert_eq!(vwap.update(&candle(21.0, 19.0, 20.0, 300.0)), Some(17.75));
+        // Without a volume the candle is left out
+        assert_eq!(vwap.update(&Candlestick::ohlc(1.0, 1.0, 1.0, 1.0)), None);
+        assert_eq!(vwap.update(&candle(17.75, 17.75, 17.75, 50.0)), Some(17.75));
+
+        vwap.reset();
+        assert_eq!(vwap.update(&candle(5.0, 5.0, 5.0, 0.0)), None);
+        assert_eq!(vwap.update(&candle(6.0, 6.0, 6.0, 1.0)), Some(6.0));
+    }
+}
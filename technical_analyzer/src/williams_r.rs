@@ -0,0 +1,102 @@
+use crate::candlestick::Candlestick;
+use crate::high_low::HighLowWindow;
+use crate::indicator::Indicator;
+use crate::utils::round_to;
+
+pub struct WilliamsR {
+    window: HighLowWindow,
+}
+
+impl WilliamsR {
+    // Panics if `period` is 0, which has no range.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            window: HighLowWindow::new(period),
+        }
+    }
+
+    // Ranges from -100 with the close at the lowest low to 0 with the close at the highest
+    // high of the last `period` candles. A window without any range gives -50.
+    pub fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        self.window.push(candle.high, candle.low);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let highest = self.window.highest();
+        let lowest = self.window.lowest();
+        if highest == lowest {
+            return Some(-50.0);
+        }
+        Some(round_to(
+            -100.0 * (highest - candle.close) / (highest - lowest),
+            8,
+        ))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Indicator for WilliamsR {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        WilliamsR::update(self, candle)
+    }
+
+    fn reset(&mut self) {
+        WilliamsR::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_from_minus_100_to_0() {
+        let mut williams_r = WilliamsR::new(2);
+        assert_eq!(
+            williams_r.update(&Candlestick::ohlc(1.0, 2.0, 1.0, 1.0)),
+            None
+        );
+        assert_eq!(
+            williams_r.update(&Candlestick::ohlc(1.0, 3.0, 1.0, 3.0)),
+            Some(0.0)
+        );
+        assert_eq!(
+            williams_r.update(&Candlestick::ohlc(1.0, 3.0, 1.0, 1.0)),
+            Some(-100.0)
+        );
+        assert_eq!(
+            williams_r.update(&Candlestick::ohlc(1.0, 3.0, 1.0, 2.5)),
+            Some(-25.0)
+        );
+        assert_eq!(
+            williams_r.update(&Candlestick::ohlc(2.0, 2.0, 2.0, 2.0)),
+            Some(-50.0)
+        );
+    }
+
+    #[test]
+    fn flat_range_is_the_middle() {
+        let mut williams_r = WilliamsR::new(1);
+        assert_eq!(
+            williams_r.update(&Candlestick::ohlc(1.0, 1.0, 1.0, 1.0)),
+            Some(-50.0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        WilliamsR::new(0);
+    }
+}
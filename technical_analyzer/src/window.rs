@@ -0,0 +1,95 @@
+use alloc::collections::VecDeque;
+
+// The last `capacity` values pushed, oldest first. Most windowed indicators keep their
+// inputs in one of these.
+pub struct Window<T> {
+    capacity: usize,
+    values: VecDeque<T>,
+}
+
+impl<T> Window<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            values: VecDeque::with_capacity(capacity + 1),
+        }
+    }
+
+    // Add a value, returning the oldest one when it falls out of the window.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        self.values.push_back(value);
+        if self.values.len() > self.capacity {
+            return self.values.pop_front();
+        }
+        None
+    }
+
+    // Oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.values.iter()
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.values.front()
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.values.back()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() >= self.capacity
+    }
+
+    // Values still to be pushed before the window is full.
+    pub fn remaining(&self) -> usize {
+        self.capacity.saturating_sub(self.values.len())
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_the_oldest_value() {
+        let mut window = Window::new(2);
+        assert!(window.is_empty());
+        assert_eq!(window.remaining(), 2);
+        assert_eq!(window.push(1), None);
+        assert_eq!(window.push(2), None);
+        assert!(window.is_full());
+        assert_eq!(window.push(3), Some(1));
+        assert_eq!((window.oldest(), window.newest()), (Some(&2), Some(&3)));
+        assert!(window.iter().eq([2, 3].iter()));
+        assert_eq!((window.len(), window.capacity()), (2, 2));
+
+        window.clear();
+        assert_eq!(window.newest(), None);
+        assert_eq!(window.remaining(), 2);
+    }
+
+    #[test]
+    fn an_empty_window_is_always_full() {
+        let mut window = Window::new(0);
+        assert!(window.is_full());
+        assert_eq!(window.push('a'), Some('a'));
+        assert!(window.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::units::Price;
+use crate::utils::round_to;
+use crate::window::Window;
+
+// Linearly weighted moving average, the most recent close weighing `period`, the one
+// before `period - 1` and so on.
+pub struct WeightedMovingAverage {
+    period: usize,
+    window: Window<f64>,
+}
+
+impl WeightedMovingAverage {
+    // Panics if `period` is 0, which has no average.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be at least 1");
+        Self {
+            period,
+            window: Window::new(period),
+        }
+    }
+
+    // Returns None while fewer than `period` closes have been seen.
+    pub fn update(&mut self, close: impl Into<Price>) -> Option<f64> {
+        let close = close.into().value();
+        if !close.is_finite() {
+            return None;
+        }
+        self.window.push(close);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let weighted_sum: f64 = self
+            .window
+            .iter()
+            .zip(1..)
+            .map(|(close, weight)| close * weight as f64)
+            .sum();
+        let total_weight = (self.period * (self.period + 1) / 2) as f64;
+        Some(round_to(weighted_sum / total_weight, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Indicator for WeightedMovingAverage {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        WeightedMovingAverage::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        WeightedMovingAverage::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighs_recent_closes_more() {
+        let mut wma = WeightedMovingAverage::new(3);
+        assert_eq!(wma.update(1.0), None);
+        assert_eq!(wma.update(2.0), None);
+        // (1 * 1 + 2 * 2 + 3 * 3) / 6
+        assert_eq!(wma.update(3.0), Some(2.33333333));
+        // (2 * 1 + 3 * 2 + 9 * 3) / 6
+        assert_eq!(wma.update(9.0), Some(5.83333333));
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be at least 1")]
+    fn zero_period_is_rejected() {
+        WeightedMovingAverage::new(0);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::candlestick::Candlestick;
+use crate::indicator::Indicator;
+use crate::rolling_std_dev::RollingStdDev;
+use crate::utils::round_to;
+
+// How many population standard deviations a value is away from the mean of the last
+// `period` values, including itself. As an `Indicator` it scores the closes, otherwise
+// any series can be fed, e.g. a pair's `spread`. Since the value pulls the mean and the
+// deviation its way, the score never goes beyond the root of `period - 1`: it takes a
+// period of at least 6 to ever reach 2.
+pub struct RollingZScore {
+    window: RollingStdDev,
+}
+
+impl RollingZScore {
+    // Panics if `period` is 0, see `RollingStdDev::new`.
+    pub fn new(period: usize) -> Self {
+        Self {
+            window: RollingStdDev::new(period),
+        }
+    }
+
+    // Returns None until the window is full, and 0 when the window has no deviation.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if !value.is_finite() {
+            return None;
+        }
+        let std_dev = self.window.update(value)?;
+        let mean = self.window.mean()?;
+        if std_dev == 0.0 {
+            return Some(0.0);
+        }
+        Some(round_to((value - mean) / std_dev, 8))
+    }
+
+    pub fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+impl Indicator for RollingZScore {
+    type Output = f64;
+
+    fn update(&mut self, candle: &Candlestick) -> Option<f64> {
+        RollingZScore::update(self, candle.close)
+    }
+
+    fn reset(&mut self) {
+        RollingZScore::reset(self)
+    }
+
+    fn bars_until_ready(&self) -> usize {
+        self.window.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_against_the_window() {
+        let mut zscore = RollingZScore::new(3);
+        assert_eq!(zscore.update(1.0), None);
+        assert_eq!(zscore.update(2.0), None);
+        // One above a mean of 2 with a deviation of the root of 2/3
+        assert_eq!(zscore.update(3.0), Some(1.22474487));
+        assert_eq!(zscore.update(-4.0), Some(-1.40182605));
+        assert_eq!(zscore.update(f64::NAN), None);
+
+        zscore.reset();
+        assert_eq!(zscore.bars_until_ready(), 3);
+        for _ in 0..2 {
+            zscore.update(5.0);
+        }
+        assert_eq!(zscore.update(5.0), Some(0.0));
+    }
+
+    #[test]
+    fn a_spike_stands_out_of_a_long_window() {
+        let mut zscore = RollingZScore::new(20);
+        for _ in 0..19 {
+            zscore.update(10.0);
+        }
+        // A lone outlier scores the largest a window of 20 allows, the root of 19
+        assert_eq!(zscore.update(50.0), Some(4.35889894));
+        assert_eq!(zscore.update(10.0), Some(-0.22941573));
+    }
+}
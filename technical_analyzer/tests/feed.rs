@@ -0,0 +1,128 @@
+#![cfg(feature = "websocket")]
+
+use futures_util::SinkExt;
+use serde_json::Value;
+use technical_analyzer::{
+    connect_feed, CandleFormat, Candlestick, CandlestickState, FeedError, JsonCandleFormat,
+    TimeFrame,
+};
+use tokio::net::TcpListener;
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::Message;
+
+// Serve `messages` as text to the first client, then close the connection. Returns the
+// URL to connect to.
+async fn serve(messages: Vec<String>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = accept_async(stream).await.unwrap();
+        for message in messages {
+            socket.send(Message::Text(message)).await.unwrap();
+        }
+        socket.close(None).await.unwrap();
+    });
+    format!("ws://{}", address)
+}
+
+// Kline events in the style of Binance, which nest the candle under "k" and send prices
+// as strings. Everything else, like subscription replies, is skipped.
+struct KlineFormat;
+
+impl CandleFormat for KlineFormat {
+    fn parse(&mut self, message: &str) -> Result<Option<Candlestick>, FeedError> {
+        let invalid = || FeedError::InvalidMessage(message.to_string());
+        let event: Value = serde_json::from_str(message).map_err(|_| invalid())?;
+        if event["e"] != "kline" {
+            return Ok(None);
+        }
+        let kline = &event["k"];
+        let price = |key: &str| -> Result<f64, FeedError> {
+            kline[key]
+                .as_str()
+                .and_then(|text| text.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let state = match kline["x"].as_bool() {
+            Some(true) => CandlestickState::Closed,
+            _ => CandlestickState::Open,
+        };
+        Candlestick::builder()
+            .open(price("o")?)
+            .high(price("h")?)
+            .low(price("l")?)
+            .close(price("c")?)
+            .volume(price("v")?)
+            .trades(kline["n"].as_u64().unwrap_or_default() as u32)
+            .timestamp(kline["t"].as_i64().ok_or_else(invalid)? / 1000)
+            .time_frame(TimeFrame::OneMinute)
+            .state(state)
+            .build()
+            .map(Some)
+            .map_err(|_| invalid())
+    }
+}
+
+fn kline(start_ms: i64, close: &str, closed: bool) -> String {
+    format!(
+        r#"{{"e":"kline","k":{{"t":{},"o":"100.0","h":"101.5","l":"99.5","c":"{}","v":"12.5","n":7,"x":{}}}}}"#,
+        start_ms, close, closed
+    )
+}
+
+#[tokio::test]
+async fn forwards_kline_candles() {
+    let url = serve(vec![
+        r#"{"result":null,"id":1}"#.to_string(),
+        kline(60_000, "100.5", false),
+        kline(60_000, "101.0", true),
+    ])
+    .await;
+    let mut candles = connect_feed(&url, KlineFormat).await.unwrap();
+
+    let open = candles.recv().await.unwrap().unwrap();
+    assert_eq!(open.timestamp(), Some(60));
+    assert_eq!(open.close().value(), 100.5);
+    assert_eq!(open.state(), CandlestickState::Open);
+
+    let closed = candles.recv().await.unwrap().unwrap();
+    assert_eq!(closed.close().value(), 101.0);
+    assert_eq!(
+        (
+            closed.open().value(),
+            closed.high().value(),
+            closed.low().value()
+        ),
+        (100.0, 101.5, 99.5)
+    );
+    assert_eq!(closed.volume().map(|v| v.value()), Some(12.5));
+    assert_eq!(closed.number_of_trades(), 7);
+    assert_eq!(closed.state(), CandlestickState::Closed);
+
+    assert!(candles.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn passes_on_parse_errors_and_goes_on() {
+    let url = serve(vec![
+        r#"{"timestamp":60,"open":1,"high":2,"low":0.5,"close":1.5,"closed":false}"#.to_string(),
+        r#"{"timestamp":60,"open":1,"high":2}"#.to_string(),
+        r#"{"timestamp":60,"open":1,"high":2,"low":0.5,"close":1.75,"trades":4}"#.to_string(),
+    ])
+    .await;
+    let mut candles = connect_feed(&url, JsonCandleFormat::new(TimeFrame::OneMinute))
+        .await
+        .unwrap();
+
+    let open = candles.recv().await.unwrap().unwrap();
+    assert_eq!(open.state(), CandlestickState::Open);
+    let error = candles.recv().await.unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "line 2: missing low");
+    let closed = candles.recv().await.unwrap().unwrap();
+    assert_eq!(closed.close().value(), 1.75);
+    assert_eq!(closed.number_of_trades(), 4);
+    assert_eq!(closed.state(), CandlestickState::Closed);
+
+    assert!(candles.recv().await.is_none());
+}
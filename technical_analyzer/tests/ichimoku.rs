@@ -0,0 +1,78 @@
+// Smoke test of the public API, the way a downstream crate would use it.
+
+use technical_analyzer::{
+    Candlestick, CandlestickState, IchimokuCloud, IchimokuCloudParameters, IchimokuCloudResult,
+    TimeFrame,
+};
+
+// A steady climb of one per hour, each candle ranging one above and below its close.
+fn ramp(count: i64) -> Vec<Candlestick> {
+    (0..count)
+        .map(|i| {
+            let close = 100.0 + i as f64;
+            Candlestick::new(
+                close,
+                close + 1.0,
+                close - 1.0,
+                close,
+                TimeFrame::OneHour,
+                Some(i * 3_600),
+                10,
+                None,
+                CandlestickState::Closed,
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+fn lines(result: &IchimokuCloudResult) -> [f64; 5] {
+    [
+        result.tenkan_sen,
+        result.kijun_sen,
+        result.senkou_span_a,
+        result.senkou_span_b,
+        result.chikou_span,
+    ]
+}
+
+#[test]
+fn computes_the_cloud_of_a_short_series() {
+    let candles = ramp(60);
+    let mut cloud = IchimokuCloud::new(IchimokuCloudParameters::default());
+    let results = cloud.initialize(&candles);
+
+    // The long period of 52 candles has to fill first
+    assert!(results[..51].iter().all(|(_, result)| result.is_none()));
+    let first = results[51].1.as_ref().unwrap();
+    assert_eq!(lines(first), [147.0, 138.5, 142.75, 125.5, 151.0]);
+    assert_eq!(first.projected_timestamp, Some(77 * 3_600));
+    assert_eq!(first.current_span_a, None);
+
+    let last = results[59].1.as_ref().unwrap();
+    assert_eq!(lines(last), [155.0, 146.5, 150.75, 133.5, 159.0]);
+    assert_eq!(cloud.projected_cloud().len(), 9);
+}
+
+#[test]
+fn an_open_candle_is_not_committed() {
+    let candles = ramp(60);
+    let mut cloud = IchimokuCloud::new(IchimokuCloudParameters::new(9, 26, 52).unwrap());
+    cloud.warm_up(&candles[..59]);
+
+    let forming = Candlestick::builder()
+        .open(158.0)
+        .high(200.0)
+        .low(157.0)
+        .close(199.0)
+        .time_frame(TimeFrame::OneHour)
+        .timestamp(59 * 3_600)
+        .state(CandlestickState::Open)
+        .build()
+        .unwrap();
+    let provisional = cloud.calculate(&forming).unwrap();
+    assert_eq!(provisional.tenkan_sen, 175.0);
+
+    let closed = cloud.calculate(&candles[59]).unwrap();
+    assert_eq!(lines(&closed), [155.0, 146.5, 150.75, 133.5, 159.0]);
+}
@@ -0,0 +1,151 @@
+// Property tests over random but valid candle series: the indicators never panic, never
+// turn finite input into NaN or infinity, and streaming gives the same results as the
+// batch paths.
+
+use proptest::prelude::*;
+use technical_analyzer::{
+    Candlestick, CandlestickState, ExponentialMovingAverage, IchimokuCloud,
+    IchimokuCloudParameters, IchimokuCloudResult, IchimokuMode, Indicator, RelativeStrengthIndex,
+    SimpleMovingAverage, TimeFrame,
+};
+
+// A valid candle with every price between `low` and `high`, Open or Closed.
+fn candle(low: f64, high: f64) -> impl Strategy<Value = Candlestick> {
+    (low..high, low..high, low..high, low..high, any::<bool>()).prop_map(
+        |(a, b, open, close, closed)| {
+            Candlestick::builder()
+                .open(open)
+                .high(a.max(b).max(open).max(close))
+                .low(a.min(b).min(open).min(close))
+                .close(close)
+                .time_frame(TimeFrame::OneMinute)
+                .state(if closed {
+                    CandlestickState::Closed
+                } else {
+                    CandlestickState::Open
+                })
+                .build()
+                .unwrap()
+        },
+    )
+}
+
+// Series of tiny prices, ordinary ones and ones spanning many orders of magnitude.
+fn series() -> impl Strategy<Value = Vec<Candlestick>> {
+    prop_oneof![
+        prop::collection::vec(candle(1e-8, 1e-2), 0..200),
+        prop::collection::vec(candle(0.0, 1e6), 0..200),
+        prop::collection::vec(candle(1e-8, 1e15), 0..200),
+    ]
+}
+
+fn parameters(period: usize) -> IchimokuCloudParameters {
+    IchimokuCloudParameters::new(period, period + 3, period + 7).unwrap()
+}
+
+fn assert_finite<I: Indicator>(
+    mut indicator: I,
+    candles: &[Candlestick],
+    values: impl Fn(&I::Output) -> Vec<f64>,
+) {
+    for candle in candles {
+        if let Some(output) = indicator.update(candle) {
+            for value in values(&output) {
+                assert!(value.is_finite(), "{} from finite input", value);
+            }
+        }
+    }
+}
+
+fn lines(result: &IchimokuCloudResult) -> Vec<f64> {
+    vec![
+        result.tenkan_sen,
+        result.kijun_sen,
+        result.senkou_span_a,
+        result.senkou_span_b,
+        result.chikou_span,
+    ]
+}
+
+proptest! {
+    #[test]
+    fn finite_input_gives_finite_output(candles in series(), period in 1usize..30) {
+        assert_finite(SimpleMovingAverage::new(period), &candles, |v| vec![*v]);
+        assert_finite(ExponentialMovingAverage::new(period), &candles, |v| vec![*v]);
+        assert_finite(ExponentialMovingAverage::new_seeded(period), &candles, |v| vec![*v]);
+        assert_finite(RelativeStrengthIndex::new(period), &candles, |v| vec![*v]);
+        assert_finite(IchimokuCloud::new(parameters(period)), &candles, lines);
+        assert_finite(
+            IchimokuCloud::new(parameters(period)).with_mode(IchimokuMode::Ema),
+            &candles,
+            lines,
+        );
+    }
+
+    #[test]
+    fn ichimoku_stays_finite_near_f64_max(
+        candles in prop::collection::vec(candle(1e300, f64::MAX), 0..100),
+        period in 1usize..20,
+    ) {
+        assert_finite(IchimokuCloud::new(parameters(period)), &candles, lines);
+    }
+
+    #[test]
+    fn sma_stays_within_the_window(candles in series(), period in 1usize..30) {
+        let mut sma = SimpleMovingAverage::new(period);
+        for (i, candle) in candles.iter().enumerate() {
+            if let Some(average) = sma.update(candle.close()) {
+                let window = candles[i + 1 - period..=i].iter().map(|c| c.close().value());
+                let lowest = window.clone().fold(f64::INFINITY, f64::min);
+                let highest = window.fold(f64::NEG_INFINITY, f64::max);
+                // Results are rounded to 8 decimals and sums lose a little precision
+                let tolerance = highest.abs() * 1e-9 + 1e-8;
+                prop_assert!(
+                    average >= lowest - tolerance && average <= highest + tolerance,
+                    "{} outside [{}, {}]",
+                    average,
+                    lowest,
+                    highest
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rsi_stays_between_0_and_100(candles in series(), period in 1usize..30) {
+        let mut rsi = RelativeStrengthIndex::new(period);
+        for candle in &candles {
+            if let Some(value) = rsi.update(candle.close()) {
+                prop_assert!((0.0..=100.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_matches_the_batch_paths(
+        candles in series(),
+        period in 1usize..20,
+        ema in any::<bool>(),
+    ) {
+        let mode = if ema { IchimokuMode::Ema } else { IchimokuMode::Classic };
+        let batch = IchimokuCloud::new(parameters(period))
+            .with_mode(mode)
+            .initialize(&candles);
+        let mut lazy = IchimokuCloud::new(parameters(period)).with_mode(mode);
+        let lazy: Vec<_> = lazy.iter_ichimoku(&candles).collect();
+        prop_assert_eq!(&lazy, &batch);
+
+        let mut streaming = IchimokuCloud::new(parameters(period)).with_mode(mode);
+        for (candle, expected) in &batch {
+            let peeked = streaming.peek(candle);
+            let result = streaming.calculate(candle);
+            prop_assert_eq!(&result, expected);
+            prop_assert_eq!(&peeked, &result);
+        }
+
+        let last = IchimokuCloud::new(parameters(period))
+            .with_mode(mode)
+            .warm_up(&candles);
+        prop_assert_eq!(last, batch.last().and_then(|(_, result)| result.clone()));
+    }
+}